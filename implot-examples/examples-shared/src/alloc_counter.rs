@@ -0,0 +1,33 @@
+//! This module contains a global allocator that counts allocations, so the demos can show how
+//! many allocations different ways of plotting cause. The demo binaries register it with
+//! `#[global_allocator]`; without that, no allocations are counted.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Number of allocations made through [`CountingAllocator`] so far
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Allocator that forwards to the system allocator and counts the allocations it makes.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Get the number of allocations made so far, if [`CountingAllocator`] is the global allocator.
+pub fn allocations() -> usize {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
@@ -1,7 +1,9 @@
+pub mod alloc_counter;
 pub mod bar_plots;
 pub mod heatmaps;
 pub mod line_plots;
 pub mod scatter_plots;
+mod shaded_plots;
 pub mod stairs_plots;
 mod stem_plots;
 pub mod text_plots;
@@ -67,6 +69,10 @@ impl DemoState {
                 ui.separator();
                 ui.text("Stem plots:");
                 stem_plots::show_demo_headers(ui, plot_ui);
+
+                ui.separator();
+                ui.text("Shaded plots:");
+                shaded_plots::show_demo_headers(ui, plot_ui);
             });
     }
 }
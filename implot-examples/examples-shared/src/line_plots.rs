@@ -4,25 +4,46 @@
 use imgui::{CollapsingHeader, Condition, Ui};
 use implot::{
     get_plot_limits, get_plot_mouse_position, get_plot_query, is_legend_entry_hovered,
-    is_plot_hovered, is_plot_queried, pixels_to_plot_vec2, plot_to_pixels_vec2, push_style_color,
-    push_style_var_f32, push_style_var_i32, set_colormap_from_preset, set_colormap_from_vec,
-    set_plot_y_axis, AxisFlags, Colormap, ImPlotLimits, ImPlotPoint, ImPlotRange, ImVec2, ImVec4,
-    Marker, Plot, PlotColorElement, PlotFlags, PlotLine, PlotLocation, PlotOrientation, PlotUi,
-    StyleVar, YAxisChoice,
+    is_plot_hovered, is_plot_queried, pixels_to_plot_vec2, plot_on_axis, plot_to_pixels_vec2,
+    push_marker, push_style_color, push_style_var_f32, set_axis, set_colormap_from_preset,
+    set_colormap_from_vec, set_plot_y_axis, Axis, AxisFlags, CachedTicks, Colormap, ImPlotLimits,
+    ImPlotPoint, ImPlotRange, ImVec2, ImVec4, Marker, Plot, PlotColorElement, PlotFlags, PlotLine,
+    PlotLocation, PlotOrientation, PlotUi, StyleVar, XAxisSide, YAxisChoice, YAxisSide,
 };
 
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, rc::Rc, time::Instant};
+
+/// Number of samples in the large signal of the getter demo.
+const LARGE_SIGNAL_SAMPLES: i32 = 10_000_000;
+
+/// Number of custom ticks in the tick setup demo.
+const MANY_TICKS: usize = 500;
 
 /// State of the line plots demo.
 pub struct LinePlotDemoState {
     linked_limits: Rc<RefCell<ImPlotRange>>,
+    /// Whether the getter demo copies its signal into slices, for comparison
+    copy_large_signal: bool,
+    /// Positions and labels of the ticks in the tick setup demo
+    many_tick_labels: Vec<(f64, String)>,
+    /// The same ticks, converted once for all frames
+    many_ticks_cached: CachedTicks,
+    /// Whether the tick setup demo uses the cached ticks
+    use_cached_ticks: bool,
 }
 
 impl LinePlotDemoState {
     /// Create a new line plots demo state object with default values in it.
     pub fn new() -> Self {
+        let many_tick_labels: Vec<(f64, String)> = (0..MANY_TICKS)
+            .map(|i| (i as f64, format!("tick {}", i)))
+            .collect();
         Self {
             linked_limits: Rc::new(RefCell::new(ImPlotRange { Min: 0.0, Max: 1.0 })),
+            copy_large_signal: false,
+            many_ticks_cached: CachedTicks::new(&many_tick_labels, false),
+            many_tick_labels,
+            use_cached_ticks: false,
         }
     }
 
@@ -74,6 +95,43 @@ impl LinePlotDemoState {
             });
     }
 
+    pub fn show_three_yaxis_plot(ui: &Ui, plot_ui: &PlotUi) {
+        ui.text("This header shows a plot with three Y axes, each with its own data and color.");
+        ui.text("Pan or zoom over an axis to only change that axis.");
+        let content_width = ui.window_content_region_width();
+        let x_positions: Vec<f64> = (0..500).map(|i| i as f64 * 0.02).collect();
+        let temperature: Vec<f64> = x_positions.iter().map(|x| 20.0 + 5.0 * x.sin()).collect();
+        let pressure: Vec<f64> = x_positions
+            .iter()
+            .map(|x| 1000.0 + 30.0 * (0.5 * x).cos())
+            .collect();
+        let humidity: Vec<f64> = x_positions.iter().map(|x| 0.5 + 0.4 * (2.0 * x).sin()).collect();
+        // One color per series, so it is clear which axis a series belongs to
+        let series = [
+            (Axis::Y1, "temperature [C]", [0.9, 0.3, 0.2], &temperature),
+            (Axis::Y2, "pressure [hPa]", [0.2, 0.6, 0.9], &pressure),
+            (Axis::Y3, "humidity", [0.3, 0.8, 0.3], &humidity),
+        ];
+
+        Plot::new("Three Y axis plot")
+            .size([content_width, 300.0])
+            .with_y_axis_label(Axis::Y1, series[0].1)
+            .with_y_axis_label(Axis::Y2, series[1].1)
+            .with_y_axis_label(Axis::Y3, series[2].1)
+            // The first Y axis stays on the left, the other two go to the right
+            .with_y_axis_side(Axis::Y2, YAxisSide::Right)
+            .with_y_axis_side(Axis::Y3, YAxisSide::Right)
+            .build(plot_ui, || {
+                for (axis, label, [red, green, blue], values) in series.iter() {
+                    let color = push_style_color(&PlotColorElement::Line, *red, *green, *blue, 1.0);
+                    plot_on_axis(*axis, || {
+                        PlotLine::new(label).plot(&x_positions, values);
+                    });
+                    color.pop();
+                }
+            });
+    }
+
     pub fn show_axis_equal_plot(ui: &Ui, plot_ui: &PlotUi) {
         ui.text("This plot has axis equal set (1:1 aspect ratio).");
         let content_width = ui.window_content_region_width();
@@ -263,9 +321,8 @@ impl LinePlotDemoState {
             .with_plot_flags(&(PlotFlags::NONE))
             .with_y_axis_flags(YAxisChoice::First, &(AxisFlags::NONE))
             .build(plot_ui, || {
-                // Markers can be selected as shown here. The markers are internally represented
-                // as an u32, hence this calling style.
-                let markerchoice = push_style_var_i32(&StyleVar::Marker, Marker::Cross as i32);
+                // Markers can be selected as shown here.
+                let markerchoice = push_marker(Marker::Cross);
                 PlotLine::new("Left eye").plot(&[2.0, 2.0], &[2.0, 1.0]);
                 // Calling pop() on the return value of the push above will undo the marker choice.
                 markerchoice.pop();
@@ -350,6 +407,88 @@ impl LinePlotDemoState {
             });
     }
 
+    pub fn show_background_items_plot(ui: &Ui, plot_ui: &PlotUi) {
+        ui.text("This header shows how to keep some items from being highlighted.");
+        let content_width = ui.window_content_region_width();
+        Plot::new("Background items plot")
+            .size([content_width, 300.0])
+            .build(plot_ui, || {
+                let x_positions = vec![0.0, 1.0, 2.0, 3.0];
+
+                // LegendFlags::NO_HIGHLIGHT_ITEM would switch highlighting off for all items, so
+                // instead the background items get labels starting with "##". That keeps them out
+                // of the legend, and with that they are never highlighted either.
+                let faint = push_style_color(&PlotColorElement::Line, 0.5, 0.5, 0.5, 0.3);
+                for offset in 0..5 {
+                    let y_positions: Vec<f64> =
+                        x_positions.iter().map(|x| x * 0.1 + offset as f64).collect();
+                    PlotLine::new(&format!("##background {}", offset))
+                        .plot(&x_positions, &y_positions);
+                }
+
+                // To still list the background items in the legend, add a manual entry with the
+                // same color. Its only point is NaN, so nothing is drawn for it.
+                PlotLine::new("background").plot(&[f64::NAN], &[f64::NAN]);
+                faint.pop();
+
+                // Regular items are highlighted as usual when hovering their legend entry
+                PlotLine::new("foreground").plot(&x_positions, &[0.0, 2.0, 1.0, 4.0]);
+            });
+    }
+
+    pub fn show_range_fit_plot(ui: &Ui, plot_ui: &PlotUi) {
+        ui.text("This header shows Y axes that only fit to the data in the visible X range.");
+        ui.text("Pan or zoom along X and watch both Y axes rescale to the visible part of the data.");
+        let content_width = ui.window_content_region_width();
+        let x_positions: Vec<f64> = (0..1000).map(|i| i as f64 * 0.01).collect();
+        let growing: Vec<f64> = x_positions.iter().map(|x| x * x * x.sin()).collect();
+        let shrinking: Vec<f64> = x_positions.iter().map(|x| (-x).exp() * x.cos()).collect();
+        Plot::new("Range fit plot")
+            .size([content_width, 300.0])
+            .x_limits(ImPlotRange { Min: 0.0, Max: 2.0 }, Condition::Once, Axis::X1)
+            .with_y_range_fit(Axis::Y1)
+            .with_y_range_fit(Axis::Y2)
+            .with_y_axis_label(Axis::Y2, "shrinking")
+            .build(plot_ui, || {
+                set_axis(Axis::Y1);
+                PlotLine::new("growing").plot(&x_positions, &growing);
+                set_axis(Axis::Y2);
+                PlotLine::new("shrinking").plot(&x_positions, &shrinking);
+            });
+    }
+
+    pub fn show_dual_x_axis_plot(ui: &Ui, plot_ui: &PlotUi) {
+        ui.text("This header shows the same data against sample indices and time at once.");
+        let content_width = ui.window_content_region_width();
+        // Samples taken every 20 ms, starting at 1.5 seconds
+        let (sampling_interval, start_time) = (0.02, 1.5);
+        let indices: Vec<f64> = (0..200).map(|i| i as f64).collect();
+        let samples: Vec<f64> = indices.iter().map(|i| (i * 0.1).sin()).collect();
+        Plot::new("Dual X axis plot")
+            .size([content_width, 300.0])
+            .with_x_axis_label(Axis::X1, "sample index")
+            .with_x_axis_label(Axis::X2, "time [s]")
+            .with_x_axis_mirror(Axis::X2, sampling_interval, start_time)
+            .with_x_axis_side(Axis::X2, XAxisSide::Top)
+            .build(plot_ui, || {
+                // The data is plotted against the first X axis only, the second one follows it
+                PlotLine::new("signal").plot(&indices, &samples);
+            });
+    }
+
+    pub fn show_appearing_limits_plot(ui: &Ui, plot_ui: &PlotUi) {
+        ui.text("This header shows limits that are reset whenever the plot appears.");
+        ui.text("Pan or zoom, then close and reopen this header to see the limits reset.");
+        let content_width = ui.window_content_region_width();
+        Plot::new("Appearing limits plot")
+            .size([content_width, 300.0])
+            .x_limits(ImPlotRange { Min: 0.0, Max: 1.0 }, Condition::Appearing, Axis::X1)
+            .y_limits(ImPlotRange { Min: 0.0, Max: 1.0 }, Condition::Appearing, Axis::Y1)
+            .build(plot_ui, || {
+                PlotLine::new("legend label").plot(&[0.1, 0.9], &[0.1, 0.9]);
+            });
+    }
+
     pub fn show_linked_x_axis_plots(&mut self, ui: &Ui, plot_ui: &PlotUi) {
         ui.text("These plots have their X axes linked, but not the Y axes");
         let content_width = ui.window_content_region_width();
@@ -371,6 +510,79 @@ impl LinePlotDemoState {
             });
     }
 
+    pub fn show_getter_plot(&mut self, ui: &Ui, plot_ui: &PlotUi) {
+        ui.text("This header plots a signal of 10 million samples that are computed on demand.");
+        ui.text_wrapped(
+            "Tick the checkbox to copy the samples into slices first instead, and compare the \
+             time it takes and the memory needed for the copy.",
+        );
+        ui.checkbox("Copy into slices", &mut self.copy_large_signal);
+        let content_width = ui.window_content_region_width();
+        let sample = |idx: i32| {
+            let t = idx as f64 * 1e-6;
+            ImPlotPoint {
+                X: t,
+                Y: (t * 20.0).sin() + 0.2 * (t * 1000.0).sin(),
+            }
+        };
+
+        let start = Instant::now();
+        let mut copied_bytes = 0;
+        Plot::new("Getter plot")
+            .size([content_width, 300.0])
+            .build(plot_ui, || {
+                if self.copy_large_signal {
+                    let (x, y): (Vec<f64>, Vec<f64>) = (0..LARGE_SIGNAL_SAMPLES)
+                        .map(|idx| {
+                            let point = sample(idx);
+                            (point.X, point.Y)
+                        })
+                        .unzip();
+                    copied_bytes = (x.capacity() + y.capacity()) * std::mem::size_of::<f64>();
+                    PlotLine::new("signal").plot(&x, &y);
+                } else {
+                    PlotLine::new("signal").plot_with_getter(LARGE_SIGNAL_SAMPLES, sample);
+                }
+            });
+        ui.text(format!(
+            "Plotted in {:.1} ms, with {:.1} MB of copied samples",
+            start.elapsed().as_secs_f64() * 1000.0,
+            copied_bytes as f64 / 1e6
+        ));
+    }
+
+    pub fn show_many_ticks_plot(&mut self, ui: &Ui, plot_ui: &PlotUi) {
+        ui.text("This header sets up 500 custom ticks with labels on every frame.");
+        ui.text_wrapped(
+            "By default, the labels are converted for ImPlot on every frame. Tick the checkbox \
+             to use ticks converted once with CachedTicks instead, and compare the allocations \
+             and the time it takes to set up and draw the plot.",
+        );
+        ui.checkbox("Use cached ticks", &mut self.use_cached_ticks);
+        let content_width = ui.window_content_region_width();
+
+        let visible = ImPlotRange { Min: 0.0, Max: 20.0 };
+
+        let allocations_before = crate::alloc_counter::allocations();
+        let start = Instant::now();
+        let plot = Plot::new("Many ticks plot")
+            .size([content_width, 300.0])
+            .x_limits(visible, Condition::FirstUseEver, Axis::X1);
+        let plot = if self.use_cached_ticks {
+            plot.x_ticks_cached(Axis::X1, &self.many_ticks_cached)
+        } else {
+            plot.x_ticks_with_labels(Axis::X1, &self.many_tick_labels, false)
+        };
+        plot.build(plot_ui, || {
+            PlotLine::new("line").plot(&[0.0, MANY_TICKS as f64], &[0.0, 1.0]);
+        });
+        ui.text(format!(
+            "{} allocations, {:.3} ms",
+            crate::alloc_counter::allocations() - allocations_before,
+            start.elapsed().as_secs_f64() * 1000.0
+        ));
+    }
+
     pub fn show_demo_headers(&mut self, ui: &Ui, plot_ui: &PlotUi) {
         if CollapsingHeader::new("Line plot: Basic").build(ui) {
             Self::show_basic_plot(ui, plot_ui);
@@ -390,15 +602,36 @@ impl LinePlotDemoState {
         if CollapsingHeader::new("Line plot: Multiple Y Axes").build(ui) {
             Self::show_two_yaxis_plot(ui, plot_ui);
         }
+        if CollapsingHeader::new("Line plot: Three Y axes").build(ui) {
+            Self::show_three_yaxis_plot(ui, plot_ui);
+        }
         if CollapsingHeader::new("Line plot: \"Axis equal\"").build(ui) {
             Self::show_axis_equal_plot(ui, plot_ui);
         }
         if CollapsingHeader::new("Line plot: Range conversions").build(ui) {
             Self::show_conversions_plot(ui, plot_ui);
         }
+        if CollapsingHeader::new("Line plot: Background items").build(ui) {
+            Self::show_background_items_plot(ui, plot_ui);
+        }
+        if CollapsingHeader::new("Line plot: Range fit").build(ui) {
+            Self::show_range_fit_plot(ui, plot_ui);
+        }
+        if CollapsingHeader::new("Line plot: Dual X axes").build(ui) {
+            Self::show_dual_x_axis_plot(ui, plot_ui);
+        }
+        if CollapsingHeader::new("Line plot: Limits on appearing").build(ui) {
+            Self::show_appearing_limits_plot(ui, plot_ui);
+        }
         if CollapsingHeader::new("Line plot: Linked plots").build(ui) {
             self.show_linked_x_axis_plots(ui, plot_ui);
         }
+        if CollapsingHeader::new("Line plot: Large signal from a getter").build(ui) {
+            self.show_getter_plot(ui, plot_ui);
+        }
+        if CollapsingHeader::new("Line plot: Many custom ticks").build(ui) {
+            self.show_many_ticks_plot(ui, plot_ui);
+        }
     }
 }
 
@@ -0,0 +1,45 @@
+//! This example demonstrates how shaded plots are to be used. For more general
+//! features of the libray, see the line_plots example.
+
+use imgui::{CollapsingHeader, Ui};
+use implot::{Plot, PlotLine, PlotShaded, PlotUi};
+
+pub fn show_basic_plot(ui: &Ui, plot_ui: &PlotUi) {
+    ui.text("This header shows a region shaded down to a reference value.");
+    let content_width = ui.window_content_region_width();
+    Plot::new("Shaded plot")
+        // The size call could also be omitted, though the defaults don't consider window
+        // width, which is why we're not doing so here.
+        .size([content_width, 300.0])
+        .build(plot_ui, || {
+            // If this is called outside a plot build callback, the program will panic.
+            let x_positions = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6];
+            let values = vec![0.3, 0.5, 0.4, 0.7, 0.6, 0.8];
+            PlotShaded::new("legend label")
+                .with_reference_y(0.2)
+                .plot(&x_positions, &values);
+        });
+}
+
+pub fn show_band_plot(ui: &Ui, plot_ui: &PlotUi) {
+    ui.text("This header shows a band between a lower and an upper bound.");
+    let content_width = ui.window_content_region_width();
+    Plot::new("Band plot")
+        .size([content_width, 300.0])
+        .build(plot_ui, || {
+            let x_positions: Vec<f64> = (0..50).map(|i| i as f64 * 0.1).collect();
+            let values: Vec<f64> = x_positions.iter().map(|x| x.sin()).collect();
+            let lower: Vec<f64> = values.iter().map(|y| y - 0.25).collect();
+            let upper: Vec<f64> = values.iter().map(|y| y + 0.25).collect();
+            // The band is drawn first, so the line ends up on top of it
+            PlotShaded::new("bounds").plot_between(&x_positions, &lower, &upper);
+            PlotLine::new("value").plot(&x_positions, &values);
+        });
+}
+
+pub fn show_demo_headers(ui: &Ui, plot_ui: &PlotUi) {
+    if CollapsingHeader::new("Shaded plots").build(ui) {
+        show_basic_plot(ui, plot_ui);
+        show_band_plot(ui, plot_ui);
+    }
+}
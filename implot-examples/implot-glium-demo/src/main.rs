@@ -4,6 +4,11 @@ use implot::Context;
 // The actual backend-specific code is in this.
 mod support;
 
+// Counts allocations for the demos that compare them
+#[global_allocator]
+static ALLOCATOR: examples_shared::alloc_counter::CountingAllocator =
+    examples_shared::alloc_counter::CountingAllocator;
+
 fn main() {
     let system = support::init(file!());
     let mut showing_demo = false;
@@ -9,6 +9,10 @@ use imgui_sys;
 use std::ops::Range;
 include!("bindings.rs");
 
+/// Version of the vendored ImPlot, as defined by `IMPLOT_VERSION` in implot.h. Bindgen does not
+/// generate string constants for it, so this is kept in sync by hand, which a test checks.
+pub const IMPLOT_VERSION: &str = "0.16";
+
 impl From<Range<f64>> for ImPlotRange {
     fn from(from: Range<f64>) -> Self {
         ImPlotRange {
@@ -83,4 +87,21 @@ mod tests {
         assert_eq!(im_range.Min, imvec.x as f64);
         assert_eq!(im_range.Max, imvec.y as f64);
     }
+
+    #[test]
+    fn test_implot_version_matches_header() {
+        let header = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/third-party/cimplot/implot/implot.h"
+        ))
+        .expect("Could not read implot.h. Did you forget to fetch git submodules?");
+        let define = header
+            .lines()
+            .find(|line| line.starts_with("#define IMPLOT_VERSION "))
+            .expect("implot.h does not define IMPLOT_VERSION");
+        assert_eq!(
+            define.trim_start_matches("#define IMPLOT_VERSION ").trim(),
+            format!("\"{}\"", IMPLOT_VERSION)
+        );
+    }
 }
\ No newline at end of file
@@ -0,0 +1,84 @@
+//! # Auto scroll module
+//!
+//! This module defines [`AutoScrollX`], a helper for live plots whose X range follows the newest
+//! data, until the user pans or zooms to look at older data.
+use crate::{is_plot_hovered, sys, Condition, Plot};
+
+/// Keeps the first X axis of a plot showing a fixed window ending at the newest data, for
+/// streaming data such as live measurements. When the user pans or zooms the plot, following is
+/// paused so they can look at older data, and it is resumed when they double-click the plot
+/// (which also fits the plot to the data in ImPlot) or [`AutoScrollX::resume`] is called.
+///
+/// Keep this around across frames, and use it like this:
+/// ```no_run
+/// # use implot::{AutoScrollX, Plot, PlotLine, PlotUi};
+/// # fn draw(plot_ui: &PlotUi, follow: &mut AutoScrollX, x: &[f64], y: &[f64]) {
+/// let latest = x.last().copied().unwrap_or(0.0);
+/// follow.apply(Plot::new("Live data"), latest).build(plot_ui, || {
+///     follow.update();
+///     PlotLine::new("Signal").plot(x, y);
+/// });
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AutoScrollX {
+    /// Width of the X range that is shown while following
+    window: f64,
+    /// Whether following is paused because the user interacted with the plot
+    paused: bool,
+}
+
+impl AutoScrollX {
+    /// Create a helper that shows the last `window` units of X data.
+    pub fn new(window: f64) -> Self {
+        Self {
+            window,
+            paused: false,
+        }
+    }
+
+    /// Change the width of the X range that is shown while following.
+    pub fn set_window(&mut self, window: f64) {
+        self.window = window;
+    }
+
+    /// Whether the X range currently follows the newest data, as opposed to being paused.
+    pub fn is_following(&self) -> bool {
+        !self.paused
+    }
+
+    /// Resume following the newest data, for example from a "Follow" button.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Set the X limits of the first X axis of the plot to `[latest - window, latest]`, unless
+    /// following is paused. `latest` is the X value of the newest data point. While following,
+    /// the user cannot pan or zoom the X axis by itself; trying to is what pauses following.
+    pub fn apply<'a>(&self, plot: Plot<'a>, latest: f64) -> Plot<'a> {
+        if self.paused {
+            plot
+        } else {
+            plot.x1_limits([latest - self.window, latest], Condition::Always)
+        }
+    }
+
+    /// Check whether the user is interacting with the current plot, and pause or resume
+    /// following accordingly. Dragging on the plot area (panning or box selection) and scrolling
+    /// over it pause following, double-clicking it resumes following. Call this in the closure
+    /// passed to [`Plot::build`] of the plot that [`AutoScrollX::apply`] was used on.
+    pub fn update(&mut self) {
+        if !is_plot_hovered() {
+            return;
+        }
+        let io = unsafe { &*sys::igGetIO() };
+        let dragging = (io.MouseDown[0] || io.MouseDown[1])
+            && (io.MouseDelta.x != 0.0 || io.MouseDelta.y != 0.0);
+        let scrolling = io.MouseWheel != 0.0 || io.MouseWheelH != 0.0;
+        if io.MouseDoubleClicked[0] {
+            self.paused = false;
+        } else if dragging || scrolling {
+            self.paused = true;
+        }
+    }
+}
@@ -0,0 +1,227 @@
+//! # Callback module
+//!
+//! This module contains the internal machinery used by every API that hands a Rust closure to
+//! ImPlot as a C callback (formatters, getters, transforms and so on). Unwinding a Rust panic
+//! through the C++ code is undefined behavior, so all of those callbacks are routed through
+//! [`CallbackData`], which catches panics on the way into C++ and re-raises them once the FFI
+//! call has returned to Rust.
+use crate::sys::ImPlotPoint;
+use std::any::Any;
+use std::os::raw::{c_char, c_int, c_void};
+use std::panic::{self, AssertUnwindSafe};
+
+/// Holds a user closure that is passed to ImPlot as `user_data`, together with the payload of
+/// the first panic that happened while calling it, if any.
+pub(crate) struct CallbackData<F> {
+    callback: F,
+    panic: Option<Box<dyn Any + Send + 'static>>,
+}
+
+impl<F> CallbackData<F> {
+    /// Wrap a closure so it can be handed over to ImPlot.
+    pub(crate) fn new(callback: F) -> Self {
+        Self {
+            callback,
+            panic: None,
+        }
+    }
+
+    /// Get the pointer to pass to ImPlot as `user_data`. The pointer is only valid for as long
+    /// as `self` is neither moved nor dropped.
+    pub(crate) fn as_user_data(&mut self) -> *mut c_void {
+        self as *mut Self as *mut c_void
+    }
+
+    /// Call the wrapped closure from within a C callback. If the closure panics, the panic is
+    /// stored and `fallback` is returned instead. Once a panic has been stored, the closure is
+    /// not called again and `fallback` is returned straight away.
+    ///
+    /// # Safety
+    /// `user_data` has to be a pointer obtained from [`CallbackData::as_user_data`] on a
+    /// `CallbackData<F>` that is still alive.
+    pub(crate) unsafe fn invoke<R>(
+        user_data: *mut c_void,
+        fallback: R,
+        call: impl FnOnce(&mut F) -> R,
+    ) -> R {
+        let data = &mut *(user_data as *mut Self);
+        if data.panic.is_some() {
+            return fallback;
+        }
+
+        let callback = &mut data.callback;
+        match panic::catch_unwind(AssertUnwindSafe(|| call(callback))) {
+            Ok(result) => result,
+            Err(payload) => {
+                data.panic = Some(payload);
+                fallback
+            }
+        }
+    }
+
+    /// Re-raise a panic that happened inside the closure, if there was one. Call this after the
+    /// FFI call the closure was used for has returned.
+    pub(crate) fn resume_panic(&mut self) {
+        if let Some(payload) = self.panic.take() {
+            panic::resume_unwind(payload);
+        }
+    }
+}
+
+/// Trampoline matching `ImPlotFormatter`, for closures turning a tick value into a label. The
+/// label is truncated to fit into the buffer ImPlot provides.
+pub(crate) unsafe extern "C" fn formatter_trampoline<F: FnMut(f64) -> String>(
+    value: f64,
+    buff: *mut c_char,
+    size: c_int,
+    user_data: *mut c_void,
+) -> c_int {
+    let label = CallbackData::<F>::invoke(user_data, String::new(), |f| f(value));
+    if buff.is_null() || size <= 0 {
+        return 0;
+    }
+
+    // Leave room for the terminating null byte, and stop at internal null bytes since C would
+    // not see anything after them anyway. Labels are cut between characters, so the multi-byte
+    // ones such as "µ" are not split into invalid UTF-8.
+    let label = label.split('\0').next().unwrap_or_default();
+    let length = label
+        .char_indices()
+        .map(|(start, c)| start + c.len_utf8())
+        .take_while(|end| *end < size as usize)
+        .last()
+        .unwrap_or(0);
+    std::ptr::copy_nonoverlapping(label.as_ptr() as *const c_char, buff, length);
+    *buff.add(length) = 0;
+    length as c_int
+}
+
+/// Trampoline matching `ImPlotTransform`, for the forward function of a pair of closures mapping
+/// one `f64` to another. ImPlot takes one `user_data` pointer for both directions of a
+/// transform, hence the pair.
+pub(crate) unsafe extern "C" fn forward_transform_trampoline<F, G>(
+    value: f64,
+    user_data: *mut c_void,
+) -> f64
+where
+    F: FnMut(f64) -> f64,
+    G: FnMut(f64) -> f64,
+{
+    CallbackData::<(F, G)>::invoke(user_data, value, |(forward, _)| forward(value))
+}
+
+/// Trampoline matching `ImPlotTransform`, for the inverse function of a pair of closures mapping
+/// one `f64` to another, see [`forward_transform_trampoline`].
+pub(crate) unsafe extern "C" fn inverse_transform_trampoline<F, G>(
+    value: f64,
+    user_data: *mut c_void,
+) -> f64
+where
+    F: FnMut(f64) -> f64,
+    G: FnMut(f64) -> f64,
+{
+    CallbackData::<(F, G)>::invoke(user_data, value, |(_, inverse)| inverse(value))
+}
+
+/// Trampoline matching `ImPlotPoint_getter`, for closures returning the point at an index. The
+/// point is written to `point`, and the pointer returned is not used by cimplot. Once the closure
+/// has panicked, NaN points are returned, which ImPlot leaves out of lines.
+pub(crate) unsafe extern "C" fn point_getter_trampoline<F: FnMut(i32) -> ImPlotPoint>(
+    user_data: *mut c_void,
+    idx: c_int,
+    point: *mut ImPlotPoint,
+) -> *mut c_void {
+    let missing = ImPlotPoint {
+        X: f64::NAN,
+        Y: f64::NAN,
+    };
+    let value = CallbackData::<F>::invoke(user_data, missing, |f| f(idx));
+    if !point.is_null() {
+        *point = value;
+    }
+    point as *mut c_void
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formatter_writes_truncated_label() {
+        let mut data: CallbackData<fn(f64) -> String> =
+            CallbackData::new(|value| format!("{:.3}", value));
+        let mut buffer = [1 as c_char; 4];
+        let written = unsafe {
+            formatter_trampoline::<fn(f64) -> String>(
+                1.0,
+                buffer.as_mut_ptr(),
+                buffer.len() as c_int,
+                data.as_user_data(),
+            )
+        };
+        assert_eq!(written, 3);
+        assert_eq!(buffer, [b'1' as c_char, b'.' as c_char, b'0' as c_char, 0]);
+        data.resume_panic();
+    }
+
+    #[test]
+    fn formatter_truncates_between_characters() {
+        let mut data: CallbackData<fn(f64) -> String> =
+            CallbackData::new(|value| format!("{}\u{b5}s", value));
+        let mut buffer = [1 as c_char; 3];
+        let written = unsafe {
+            formatter_trampoline::<fn(f64) -> String>(
+                1.0,
+                buffer.as_mut_ptr(),
+                buffer.len() as c_int,
+                data.as_user_data(),
+            )
+        };
+        // The two bytes of the micro sign do not fit in front of the null byte
+        assert_eq!(written, 1);
+        assert_eq!(buffer[..2], [b'1' as c_char, 0]);
+        data.resume_panic();
+    }
+
+    #[test]
+    #[should_panic(expected = "formatter exploded")]
+    fn panicking_formatter_is_rethrown() {
+        let mut data: CallbackData<fn(f64) -> String> =
+            CallbackData::new(|_| panic!("formatter exploded"));
+        let mut buffer = [0 as c_char; 32];
+        let written = unsafe {
+            formatter_trampoline::<fn(f64) -> String>(
+                1.0,
+                buffer.as_mut_ptr(),
+                buffer.len() as c_int,
+                data.as_user_data(),
+            )
+        };
+        // The panic must not have escaped the trampoline, and an empty label is written instead
+        assert_eq!(written, 0);
+        assert_eq!(buffer[0], 0);
+        data.resume_panic();
+    }
+
+    #[test]
+    #[should_panic(expected = "getter exploded")]
+    fn panicking_getter_is_rethrown() {
+        type Getter = Box<dyn FnMut(i32) -> ImPlotPoint>;
+        let getter: Getter = Box::new(|idx| {
+            if idx > 0 {
+                panic!("getter exploded");
+            }
+            ImPlotPoint { X: 1.0, Y: 2.0 }
+        });
+        let mut data = CallbackData::new(getter);
+        let mut point = ImPlotPoint { X: 0.0, Y: 0.0 };
+        unsafe {
+            point_getter_trampoline::<Getter>(data.as_user_data(), 0, &mut point);
+            assert_eq!((point.X, point.Y), (1.0, 2.0));
+            point_getter_trampoline::<Getter>(data.as_user_data(), 1, &mut point);
+        }
+        // The panic must not have escaped the trampoline, and a missing point is written instead
+        assert!(point.X.is_nan() && point.Y.is_nan());
+        data.resume_panic();
+    }
+}
@@ -17,6 +17,23 @@ pub struct Context {
     raw: *mut sys::ImPlotContext,
 }
 
+/// Configuration of how labels on time axes are formatted. This mirrors the corresponding
+/// booleans in ImPlotStyle.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimeFormatConfig {
+    /// Show times in the local timezone instead of UTC
+    pub use_local_time: bool,
+    /// Format dates according to ISO 8601 (e.g. "2021-03-14") instead of the US style
+    pub use_iso_8601: bool,
+    /// Show times with a 24 hour clock instead of AM/PM
+    pub use_24_hour_clock: bool,
+}
+
+/// Default size of plots, used until `Context::set_default_plot_size` is called and for plots
+/// created while there is no current context
+pub(crate) const DEFAULT_PLOT_SIZE_X: f32 = 400.0;
+pub(crate) const DEFAULT_PLOT_SIZE_Y: f32 = 400.0;
+
 // This mutex is used to guard any accesses to the context
 static CTX_MUTEX: ReentrantMutex<()> = parking_lot::const_reentrant_mutex(());
 
@@ -40,7 +57,12 @@ impl Context {
         unsafe {
             sys::ImPlot_SetCurrentContext(ctx);
         }
-        Self { raw: ctx }
+        crate::reset_stack_counts();
+        let context = Self { raw: ctx };
+        // Keep the default size this library used before it was configurable, rather than
+        // ImPlot's own default
+        context.set_default_plot_size([DEFAULT_PLOT_SIZE_X, DEFAULT_PLOT_SIZE_Y]);
+        context
     }
 
     /// Get a "plot ui" struct, this will be used to build actual plots and is quite
@@ -73,6 +95,67 @@ impl Context {
         }
     }
 
+    /// Get the current time axis formatting configuration.
+    #[rustversion::attr(since(1.48), doc(alias = "UseLocalTime"))]
+    #[rustversion::attr(since(1.48), doc(alias = "UseISO8601"))]
+    #[rustversion::attr(since(1.48), doc(alias = "Use24HourClock"))]
+    pub fn time_format(&self) -> TimeFormatConfig {
+        unsafe {
+            let style = sys::ImPlot_GetStyle();
+            assert_ne!(style, std::ptr::null_mut());
+            TimeFormatConfig {
+                use_local_time: (*style).UseLocalTime,
+                use_iso_8601: (*style).UseISO8601,
+                use_24_hour_clock: (*style).Use24HourClock,
+            }
+        }
+    }
+
+    /// Set how labels on time axes are formatted, for example to always show UTC times in
+    /// ISO 8601 format regardless of the machine's locale. This applies to all plots drawn
+    /// with this context.
+    #[rustversion::attr(since(1.48), doc(alias = "UseLocalTime"))]
+    #[rustversion::attr(since(1.48), doc(alias = "UseISO8601"))]
+    #[rustversion::attr(since(1.48), doc(alias = "Use24HourClock"))]
+    pub fn set_time_format(&self, config: TimeFormatConfig) {
+        unsafe {
+            let style = sys::ImPlot_GetStyle();
+            assert_ne!(style, std::ptr::null_mut());
+            (*style).UseLocalTime = config.use_local_time;
+            (*style).UseISO8601 = config.use_iso_8601;
+            (*style).Use24HourClock = config.use_24_hour_clock;
+        }
+    }
+
+    /// Get the size plots created with `Plot::new` get by default, as [size_x, size_y].
+    #[rustversion::attr(since(1.48), doc(alias = "PlotDefaultSize"))]
+    pub fn default_plot_size(&self) -> [f32; 2] {
+        unsafe {
+            let style = sys::ImPlot_GetStyle();
+            assert_ne!(style, std::ptr::null_mut());
+            [(*style).PlotDefaultSize.x, (*style).PlotDefaultSize.y]
+        }
+    }
+
+    /// Set the size plots created with `Plot::new` get by default, given as [size_x, size_y], so
+    /// it does not have to be set with `Plot::size` on every plot. This defaults to 400 by 400.
+    ///
+    /// The size is stored in the style of this context, so it only applies to plots created
+    /// while this context is the current one. Like everything else in ImGui and ImPlot, the
+    /// context is meant to be used from a single thread. It is also ImPlot's fallback for size
+    /// components of zero, so `Plot::size([0.0, 200.0])` gives a plot of the default width.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotDefaultSize"))]
+    pub fn set_default_plot_size(&self, size: [f32; 2]) {
+        unsafe {
+            let style = sys::ImPlot_GetStyle();
+            assert_ne!(style, std::ptr::null_mut());
+            (*style).PlotDefaultSize = sys::ImVec2 {
+                x: size[0],
+                y: size[1],
+            };
+        }
+    }
+
     /// Use classic colors for the implot style.
     ///
     /// This will eventually be exposed more thoroughly in the form of ImPlotStyle,
@@ -92,5 +175,9 @@ impl Drop for Context {
         unsafe {
             sys::ImPlot_DestroyContext(self.raw);
         }
+        // Whatever was still pushed went away with the context
+        crate::reset_stack_counts();
+        // The scratch buffers the plotting helpers borrow are only needed while plotting
+        crate::scratch::release();
     }
 }
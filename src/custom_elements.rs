@@ -0,0 +1,618 @@
+//! # Custom plot elements module
+//!
+//! This module defines plot elements that ImPlot does not have primitives for. They are drawn
+//! directly onto the plot's draw list, converting data coordinates to pixels with the current
+//! plot's axes. Each of them still adds a single entry to the legend, but since the drawing
+//! happens outside of ImPlot's item machinery, hiding them by clicking the legend entry is not
+//! supported. For the elements ImPlot provides itself, see `plot_elements`.
+use crate::{sys, ImVec2, ImVec4, IMPLOT_AUTO};
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+/// Add a legend entry for a custom element and return the color the element should be drawn
+/// in. If no color is given, the next color of the current colormap is used, same as for the
+/// elements ImPlot draws itself. The element type and point count are recorded with the item.
+pub(crate) fn legend_entry_color(
+    label: &CString,
+    color: Option<ImVec4>,
+    element_type: &'static str,
+    point_count: usize,
+) -> ImVec4 {
+    let mut item_color = ImVec4 {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        w: 0.0,
+    };
+    unsafe {
+        if let Some(color) = color {
+            sys::ImPlot_SetNextLineStyle(color, IMPLOT_AUTO as f32);
+        }
+        sys::ImPlot_PlotDummy(crate::items::legend_label(label).as_ptr(), 0);
+        sys::ImPlot_GetLastItemColor(&mut item_color as *mut ImVec4);
+    }
+    crate::items::record_last_item(label, element_type, point_count);
+    item_color
+}
+
+/// Convert a point in the current plot's coordinate system to pixels, using the current axes.
+pub(crate) fn to_pixels(x: f64, y: f64) -> ImVec2 {
+    let mut pixel_position = ImVec2 { x: 0.0, y: 0.0 }; // doesn't seem to have default()
+    unsafe {
+        sys::ImPlot_PlotToPixels_double(
+            &mut pixel_position as *mut ImVec2,
+            x,
+            y,
+            IMPLOT_AUTO,
+            IMPLOT_AUTO,
+        );
+    }
+    pixel_position
+}
+
+/// Run the given drawing function on the current plot's draw list, clipped to the plot area.
+pub(crate) fn draw_clipped<F: FnOnce(*mut sys::ImDrawList)>(f: F) {
+    unsafe {
+        sys::ImPlot_PushPlotClipRect(0.0);
+        f(sys::ImPlot_GetPlotDrawList());
+        sys::ImPlot_PopPlotClipRect();
+    }
+}
+
+/// Draw a polyline through the given pixel positions with a dash pattern, whose entries are the
+/// lengths of the alternating drawn and skipped parts in pixels. The pattern continues around the
+/// corners of the polyline. Segments with non-finite ends are skipped, which leaves gaps. Only
+/// the dashes within the `clip` rectangle are generated, so lines reaching far outside of the
+/// plot area when zoomed in do not turn into huge numbers of dashes.
+pub(crate) fn draw_dashed_polyline(
+    draw_list: *mut sys::ImDrawList,
+    points: &[ImVec2],
+    clip: (ImVec2, ImVec2),
+    pattern: &[f32],
+    color: u32,
+    weight: f32,
+) {
+    let period: f32 = pattern.iter().sum();
+    if period <= 0.0 || !period.is_finite() {
+        return;
+    }
+    // Distance along the polyline at the start of the current segment, for the pattern phase
+    let mut travelled = 0.0f32;
+    for segment in points.windows(2) {
+        let (start, end) = (segment[0], segment[1]);
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        let length = (dx * dx + dy * dy).sqrt();
+        if !length.is_finite() || length == 0.0 {
+            continue;
+        }
+        let point_at = |distance: f32| ImVec2 {
+            x: start.x + dx * distance / length,
+            y: start.y + dy * distance / length,
+        };
+
+        if let Some((t0, t1)) = clip_segment(start, end, clip) {
+            let (mut distance, end_distance) = (t0 * length, t1 * length);
+            // Find the part of the pattern the visible part of the segment starts in
+            let mut position = (travelled + distance) % period;
+            let mut index = 0;
+            while position >= pattern[index] && index + 1 < pattern.len() {
+                position -= pattern[index];
+                index += 1;
+            }
+            while distance < end_distance {
+                let part_end = (distance + pattern[index] - position).min(end_distance);
+                // Even entries of the pattern are drawn, odd ones are skipped
+                if index.is_multiple_of(2) {
+                    unsafe {
+                        sys::ImDrawList_AddLine(
+                            draw_list,
+                            point_at(distance),
+                            point_at(part_end),
+                            color,
+                            weight,
+                        );
+                    }
+                }
+                distance = part_end;
+                position = 0.0;
+                index = (index + 1) % pattern.len();
+            }
+        }
+        travelled += length;
+    }
+}
+
+/// Internal helper to clip the segment from `start` to `end` to a rectangle, returning the
+/// visible part as the range of the segment parameter from 0 at the start to 1 at the end.
+fn clip_segment(start: ImVec2, end: ImVec2, (min, max): (ImVec2, ImVec2)) -> Option<(f32, f32)> {
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    let (mut t0, mut t1) = (0.0f32, 1.0f32);
+    let boundaries = [
+        (-dx, start.x - min.x),
+        (dx, max.x - start.x),
+        (-dy, start.y - min.y),
+        (dy, max.y - start.y),
+    ];
+    for &(direction, distance) in boundaries.iter() {
+        if direction == 0.0 {
+            // Parallel to this boundary, so either fully inside or fully outside of it
+            if distance < 0.0 {
+                return None;
+            }
+        } else if direction < 0.0 {
+            t0 = t0.max(distance / direction);
+        } else {
+            t1 = t1.min(distance / direction);
+        }
+    }
+    if t0 < t1 {
+        Some((t0, t1))
+    } else {
+        None
+    }
+}
+
+/// Convert a color to the packed representation the draw list expects.
+pub(crate) fn color_to_u32(color: ImVec4) -> u32 {
+    unsafe { sys::igColorConvertFloat4ToU32(color) }
+}
+
+/// Struct to provide functionality for plotting a directional field of arrows, also known
+/// as a quiver plot.
+pub struct PlotQuiver {
+    /// Label to show in the legend for this quiver plot
+    label: CString,
+    /// Factor the `u` and `v` components are multiplied with before drawing, in plot coordinates
+    scale: f64,
+    /// Color of the arrows. If this is `None`, the next colormap color is used.
+    color: Option<ImVec4>,
+    /// Length of the arrowheads, in pixels
+    head_size: f32,
+    /// Thickness of the arrow shafts, in pixels
+    line_weight: f32,
+}
+
+impl PlotQuiver {
+    /// Create a new quiver plot to be shown. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            scale: 1.0,
+            color: None,
+            head_size: 8.0,
+            line_weight: 1.0,
+        }
+    }
+
+    /// Set the factor the arrow components are multiplied with. An arrow at `(x, y)` with
+    /// components `(u, v)` ends at `(x + scale * u, y + scale * v)` in plot coordinates.
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set the color of the arrows, instead of taking the next color from the colormap.
+    pub fn with_color(mut self, color: ImVec4) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the length of the arrowheads in pixels. The arrowheads keep this size regardless
+    /// of zoom.
+    pub fn with_head_size(mut self, head_size: f32) -> Self {
+        self.head_size = head_size;
+        self
+    }
+
+    /// Set the thickness of the arrow shafts in pixels.
+    pub fn with_line_weight(mut self, line_weight: f32) -> Self {
+        self.line_weight = line_weight;
+        self
+    }
+
+    /// Draw the arrows. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build). `x` and `y` are the positions the
+    /// arrows start at, `u` and `v` are the arrow components in X and Y direction.
+    pub fn plot(&self, x: &[f64], y: &[f64], u: &[f64], v: &[f64]) {
+        let number_of_points = x.len().min(y.len()).min(u.len()).min(v.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+
+        crate::extents::track_points(x, y);
+        let (end_x, end_y): (Vec<f64>, Vec<f64>) = (0..number_of_points)
+            .map(|i| (x[i] + self.scale * u[i], y[i] + self.scale * v[i]))
+            .unzip();
+        crate::extents::track_points(&end_x, &end_y);
+
+        let color = color_to_u32(legend_entry_color(
+            &self.label,
+            self.color,
+            "PlotQuiver",
+            number_of_points,
+        ));
+        draw_clipped(|draw_list| {
+            for i in 0..number_of_points {
+                let start = to_pixels(x[i], y[i]);
+                let end = to_pixels(end_x[i], end_y[i]);
+                let (dx, dy) = (end.x - start.x, end.y - start.y);
+                let length = (dx * dx + dy * dy).sqrt();
+                // Arrows shorter than a pixel have no meaningful direction to draw
+                if length.is_nan() || length < 1.0 {
+                    continue;
+                }
+
+                // Unit vector along the arrow and the perpendicular one, for the arrowhead
+                let (ux, uy) = (dx / length, dy / length);
+                let head = self.head_size.min(length);
+                let base = ImVec2 {
+                    x: end.x - ux * head,
+                    y: end.y - uy * head,
+                };
+                let half_width = 0.5 * head;
+                unsafe {
+                    sys::ImDrawList_AddLine(draw_list, start, base, color, self.line_weight);
+                    sys::ImDrawList_AddTriangleFilled(
+                        draw_list,
+                        end,
+                        ImVec2 {
+                            x: base.x - uy * half_width,
+                            y: base.y + ux * half_width,
+                        },
+                        ImVec2 {
+                            x: base.x + uy * half_width,
+                            y: base.y - ux * half_width,
+                        },
+                        color,
+                    );
+                }
+            }
+        });
+    }
+}
+
+/// Struct to provide functionality for plotting financial data as candlesticks, also known as
+/// an OHLC (open, high, low, close) chart.
+pub struct PlotOhlc {
+    /// Label to show in the legend for this chart
+    label: CString,
+    /// Color of candles that closed higher than they opened
+    bull_color: ImVec4,
+    /// Color of candles that closed lower than they opened
+    bear_color: ImVec4,
+    /// Width of the candle bodies, as a fraction of the X distance between consecutive candles
+    width_fraction: f64,
+    /// Width of the candle bodies in X units, which takes precedence over `width_fraction`
+    candle_width: Option<f64>,
+}
+
+impl PlotOhlc {
+    /// Create a new candlestick chart to be shown. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            bull_color: ImVec4 {
+                x: 0.0,
+                y: 1.0,
+                z: 0.441,
+                w: 1.0,
+            },
+            bear_color: ImVec4 {
+                x: 0.853,
+                y: 0.050,
+                z: 0.310,
+                w: 1.0,
+            },
+            width_fraction: 0.25,
+            candle_width: None,
+        }
+    }
+
+    /// Set the colors of candles closing higher (bull) and lower (bear) than they opened. The
+    /// legend entry uses the bull color.
+    pub fn with_colors(mut self, bull_color: ImVec4, bear_color: ImVec4) -> Self {
+        self.bull_color = bull_color;
+        self.bear_color = bear_color;
+        self
+    }
+
+    /// Set the width of the candle bodies as a fraction of the X distance between the first two
+    /// candles, so candles keep their relative width regardless of the unit of the X values.
+    /// A single candle has no neighbor to measure the distance to, so its width is taken in X
+    /// units directly; set [`PlotOhlc::with_candle_width`] for charts that may show only one.
+    pub fn with_width(mut self, width_fraction: f64) -> Self {
+        self.width_fraction = width_fraction;
+        self
+    }
+
+    /// Set the width of the candle bodies in X units, for example 86400.0 * 0.5 for daily
+    /// candles on a time axis. This takes precedence over [`PlotOhlc::with_width`], and gives
+    /// single candles a meaningful width.
+    pub fn with_candle_width(mut self, width: f64) -> Self {
+        self.candle_width = Some(width);
+        self
+    }
+
+    /// Draw the candles. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build). `time` holds the X positions of the
+    /// candles, which for a time axis are UNIX timestamps in seconds.
+    pub fn plot(&self, time: &[f64], open: &[f64], high: &[f64], low: &[f64], close: &[f64]) {
+        let number_of_points = time
+            .len()
+            .min(open.len())
+            .min(high.len())
+            .min(low.len())
+            .min(close.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+
+        crate::extents::track_points(&time[..number_of_points], &high[..number_of_points]);
+        crate::extents::track_points(&time[..number_of_points], &low[..number_of_points]);
+
+        let width = match self.candle_width {
+            Some(width) => width,
+            None if number_of_points > 1 => (time[1] - time[0]) * self.width_fraction,
+            None => self.width_fraction,
+        };
+        let half_width = width / 2.0;
+        legend_entry_color(
+            &self.label,
+            Some(self.bull_color),
+            "PlotOhlc",
+            number_of_points,
+        );
+        let bull_color = color_to_u32(self.bull_color);
+        let bear_color = color_to_u32(self.bear_color);
+        draw_clipped(|draw_list| {
+            for i in 0..number_of_points {
+                let color = if open[i] > close[i] {
+                    bear_color
+                } else {
+                    bull_color
+                };
+                let low_position = to_pixels(time[i], low[i]);
+                let high_position = to_pixels(time[i], high[i]);
+                let open_position = to_pixels(time[i] - half_width, open[i]);
+                let close_position = to_pixels(time[i] + half_width, close[i]);
+                unsafe {
+                    sys::ImDrawList_AddLine(draw_list, low_position, high_position, color, 1.0);
+                    sys::ImDrawList_AddRectFilled(
+                        draw_list,
+                        open_position,
+                        close_position,
+                        color,
+                        0.0,
+                        0,
+                    );
+                }
+            }
+        });
+    }
+}
+
+/// Struct to provide functionality for plotting a line whose thickness varies along it, for
+/// example to show the speed along a trajectory.
+pub struct PlotVariableWidthLine {
+    /// Label to show in the legend for this line
+    label: CString,
+    /// Color of the line. If this is `None`, the next colormap color is used.
+    color: Option<ImVec4>,
+}
+
+impl PlotVariableWidthLine {
+    /// Create a new line to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            color: None,
+        }
+    }
+
+    /// Set the color of the line, instead of taking the next color from the colormap.
+    pub fn with_color(mut self, color: ImVec4) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Draw the line through the points given by `x` and `y`, with `widths` giving the thickness
+    /// of the line at each point in pixels. The thickness changes linearly between points. Use
+    /// this in closures passed to [`Plot::build()`](struct.Plot.html#method.build).
+    pub fn plot(&self, x: &[f64], y: &[f64], widths: &[f32]) {
+        let number_of_points = x.len().min(y.len()).min(widths.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+
+        crate::extents::track_points(&x[..number_of_points], &y[..number_of_points]);
+        let color = color_to_u32(legend_entry_color(
+            &self.label,
+            self.color,
+            "PlotVariableWidthLine",
+            number_of_points,
+        ));
+        let points: Vec<ImVec2> = (0..number_of_points)
+            .map(|i| to_pixels(x[i], y[i]))
+            .collect();
+
+        // Unit normals of the segments, zero for segments shorter than a pixel
+        let segment_normals: Vec<(f32, f32)> = points
+            .windows(2)
+            .map(|segment| {
+                let (dx, dy) = (segment[1].x - segment[0].x, segment[1].y - segment[0].y);
+                let length = (dx * dx + dy * dy).sqrt();
+                if length.is_nan() || length < 1.0 {
+                    (0.0, 0.0)
+                } else {
+                    (-dy / length, dx / length)
+                }
+            })
+            .collect();
+        // The normal at each point is the average of the normals of the segments around it, so
+        // neighboring segments share their edges and the line has no gaps at its joints
+        let offsets: Vec<(f32, f32)> = (0..number_of_points)
+            .map(|i| {
+                let before = if i > 0 {
+                    segment_normals[i - 1]
+                } else {
+                    (0.0, 0.0)
+                };
+                let after = segment_normals.get(i).copied().unwrap_or((0.0, 0.0));
+                let (nx, ny) = (before.0 + after.0, before.1 + after.1);
+                let length = (nx * nx + ny * ny).sqrt();
+                let half_width = 0.5 * widths[i];
+                if length.is_nan() || length < 1e-6 {
+                    (0.0, 0.0)
+                } else {
+                    (nx / length * half_width, ny / length * half_width)
+                }
+            })
+            .collect();
+
+        draw_clipped(|draw_list| {
+            for i in 0..number_of_points.saturating_sub(1) {
+                let (start, end) = (points[i], points[i + 1]);
+                let (start_offset, end_offset) = (offsets[i], offsets[i + 1]);
+                unsafe {
+                    sys::ImDrawList_AddQuadFilled(
+                        draw_list,
+                        ImVec2 {
+                            x: start.x + start_offset.0,
+                            y: start.y + start_offset.1,
+                        },
+                        ImVec2 {
+                            x: end.x + end_offset.0,
+                            y: end.y + end_offset.1,
+                        },
+                        ImVec2 {
+                            x: end.x - end_offset.0,
+                            y: end.y - end_offset.1,
+                        },
+                        ImVec2 {
+                            x: start.x - start_offset.0,
+                            y: start.y - start_offset.1,
+                        },
+                        color,
+                    );
+                }
+            }
+        });
+    }
+}
+
+/// Add an entry to the legend of the current plot without drawing any data, for example for a
+/// data series that is available but not loaded yet. Pass a muted color such as gray to show the
+/// entry as available rather than shown. Like for other items, the user can toggle the entry by
+/// clicking it in the legend, and [`crate::is_legend_entry_hovered`] works with its label, which
+/// makes it suitable for "click to load" legends. Use this in closures passed to
+/// [`Plot::build()`](struct.Plot.html#method.build).
+///
+/// # Panics
+/// Will panic if the label string contains internal null bytes.
+#[rustversion::attr(since(1.48), doc(alias = "PlotDummy"))]
+pub fn legend_entry_only(label: &str, color: ImVec4) {
+    let label = CString::new(label)
+        .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label));
+    legend_entry_color(&label, Some(color), "LegendEntry", 0);
+}
+
+/// Draw text in the top left corner of the current plot area, with the default text color. This
+/// is used for overlays the plot shows by itself, outside of any plot element.
+pub(crate) fn draw_corner_text(text: &str) {
+    let plot_position = crate::get_plot_position();
+    let padding = unsafe { (*sys::ImPlot_GetStyle()).MousePosPadding };
+    let color = unsafe { sys::igGetColorU32_Col(sys::ImGuiCol_Text as i32, 1.0) };
+    draw_clipped(|draw_list| unsafe {
+        let text_begin = text.as_ptr() as *const c_char;
+        sys::ImDrawList_AddText_Vec2(
+            draw_list,
+            ImVec2 {
+                x: plot_position.x + padding.x,
+                y: plot_position.y + padding.y,
+            },
+            color,
+            text_begin,
+            text_begin.add(text.len()),
+        );
+    });
+}
+
+/// Draw tick labels for the given X axis of the current plot, rotated counter-clockwise by
+/// `angle_degrees` around their top right corner, which is placed just below the tick. Labels of
+/// ticks outside of the plot area are skipped. This is used by the plot when rotated tick labels
+/// were requested, because ImPlot itself can only draw horizontal tick labels.
+pub(crate) fn draw_rotated_x_tick_labels(
+    axis: crate::Axis,
+    angle_degrees: f32,
+    ticks: &[(f64, String)],
+) {
+    let plot_position = crate::get_plot_position();
+    let plot_size = crate::get_plot_size();
+    let padding = unsafe { (*sys::ImPlot_GetStyle()).LabelPadding };
+    let (sin, cos) = angle_degrees.to_radians().sin_cos();
+    let color = unsafe { sys::igGetColorU32_Col(sys::ImGuiCol_Text as i32, 1.0) };
+
+    unsafe {
+        let draw_list = sys::ImPlot_GetPlotDrawList();
+        for (position, label) in ticks {
+            let pivot_x = crate::plot_to_pixels_f32(*position, 0.0, axis, crate::Axis::Y1).x;
+            if pivot_x < plot_position.x || pivot_x > plot_position.x + plot_size.x {
+                continue;
+            }
+            let pivot = ImVec2 {
+                x: pivot_x,
+                y: plot_position.y + plot_size.y + padding.y,
+            };
+
+            let text_begin = label.as_ptr() as *const c_char;
+            let text_end = text_begin.add(label.len());
+            let mut text_size = ImVec2 { x: 0.0, y: 0.0 };
+            sys::igCalcTextSize(
+                &mut text_size as *mut ImVec2,
+                text_begin,
+                text_end,
+                false,
+                -1.0,
+            );
+
+            // Draw the text unrotated with its top right corner at the pivot, then rotate the
+            // vertices that were just added around the pivot.
+            let first_vertex = (*draw_list).VtxBuffer.Size;
+            sys::ImDrawList_AddText_Vec2(
+                draw_list,
+                ImVec2 {
+                    x: pivot.x - text_size.x,
+                    y: pivot.y,
+                },
+                color,
+                text_begin,
+                text_end,
+            );
+            let last_vertex = (*draw_list).VtxBuffer.Size;
+            for index in first_vertex..last_vertex {
+                let vertex = &mut *(*draw_list).VtxBuffer.Data.add(index as usize);
+                let (dx, dy) = (vertex.pos.x - pivot.x, vertex.pos.y - pivot.y);
+                // Screen coordinates point down, hence the signs for counter-clockwise rotation
+                vertex.pos = ImVec2 {
+                    x: pivot.x + dx * cos + dy * sin,
+                    y: pivot.y - dx * sin + dy * cos,
+                };
+            }
+        }
+    }
+}
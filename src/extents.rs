@@ -0,0 +1,250 @@
+//! # Extents module
+//!
+//! This module keeps track of the data-space extents of what is plotted, for plots that opted
+//! into it, and holds the [`DataExtents`] type they are exposed as. Plot elements report the
+//! data they draw here, and the plot stores the union of everything drawn during a frame under
+//! its title, so it is available on the next frame (extents are only known after everything has
+//! been plotted, but limits and constraints have to be set up before that). Extents are kept
+//! per axis, for the data plotted against that axis, so data on the secondary axes does not
+//! affect the first ones. The points reported here are passed on to item bounds tracking and
+//! call recording as well.
+//!
+//! Other state plots carry over from one frame to the next lives in the `plot_state` module.
+use crate::{
+    get_x_axis_from_index, get_x_axis_index, get_y_axis_from_index, get_y_axis_index, Axis,
+    ImPlotRange, ImPlotRect, NUMBER_OF_X_AXES, NUMBER_OF_Y_AXES,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    ffi::CString,
+};
+
+thread_local! {
+    /// Extents of the data plotted so far in the current plot, if tracking is enabled for it.
+    /// ImGui and ImPlot are single-threaded, so a thread local matches their model.
+    static CURRENT_EXTENTS: RefCell<Option<AxisExtents>> = const { RefCell::new(None) };
+
+    /// Extents of the data plotted on the most recent frame, per plot title
+    static PREVIOUS_EXTENTS: RefCell<HashMap<CString, AxisExtents>> = RefCell::new(HashMap::new());
+
+    /// Indices of the X and Y axes that data is currently plotted against
+    static CURRENT_AXES: Cell<(usize, usize)> = const { Cell::new((0, 0)) };
+}
+
+/// Bounding box of the data plotted into a plot, in plot coordinates. Only points with finite
+/// coordinates are taken into account.
+#[derive(Debug, Copy, Clone)]
+pub struct DataExtents {
+    /// Range of the X coordinates of the plotted data
+    pub x: ImPlotRange,
+    /// Range of the Y coordinates of the plotted data
+    pub y: ImPlotRange,
+}
+
+impl From<ImPlotRect> for DataExtents {
+    fn from(rect: ImPlotRect) -> Self {
+        Self {
+            x: rect.X,
+            y: rect.Y,
+        }
+    }
+}
+
+/// Extents of the data plotted into a plot, per axis. Data plotted against a pair of axes
+/// extends the ranges of both axes.
+#[derive(Copy, Clone)]
+pub(crate) struct AxisExtents {
+    x: [ImPlotRange; NUMBER_OF_X_AXES],
+    y: [ImPlotRange; NUMBER_OF_Y_AXES],
+}
+
+impl AxisExtents {
+    /// Extents that contain nothing on any axis.
+    fn empty() -> Self {
+        let range = ImPlotRange {
+            Min: f64::INFINITY,
+            Max: f64::NEG_INFINITY,
+        };
+        Self {
+            x: [range; NUMBER_OF_X_AXES],
+            y: [range; NUMBER_OF_Y_AXES],
+        }
+    }
+
+    /// Whether any point was plotted. Points extend an X and a Y axis at once, so looking at
+    /// the X axes is enough.
+    fn is_nonempty(&self) -> bool {
+        self.x.iter().any(|range| range.Min <= range.Max)
+    }
+
+    /// Extend the ranges of the given axes to contain the given rectangle.
+    fn extend(&mut self, x_index: usize, y_index: usize, rect: &ImPlotRect) {
+        let (x, y) = (&mut self.x[x_index], &mut self.y[y_index]);
+        x.Min = x.Min.min(rect.X.Min);
+        x.Max = x.Max.max(rect.X.Max);
+        y.Min = y.Min.min(rect.Y.Min);
+        y.Max = y.Max.max(rect.Y.Max);
+    }
+
+    /// The ranges of the data along each axis that data was plotted against.
+    pub(crate) fn axis_ranges(&self) -> Vec<(Axis, ImPlotRange)> {
+        let x = self
+            .x
+            .iter()
+            .enumerate()
+            .filter_map(|(index, range)| Some((get_x_axis_from_index(index)?, *range)));
+        let y = self
+            .y
+            .iter()
+            .enumerate()
+            .filter_map(|(index, range)| Some((get_y_axis_from_index(index)?, *range)));
+        x.chain(y)
+            .filter(|(_, range)| range.Min <= range.Max)
+            .collect()
+    }
+
+    /// The extents of the data along the first X and Y axes, if data was plotted against both.
+    pub(crate) fn first_axes(&self) -> Option<DataExtents> {
+        let (x, y) = (self.x[0], self.y[0]);
+        if x.Min <= x.Max && y.Min <= y.Max {
+            Some(DataExtents { x, y })
+        } else {
+            None
+        }
+    }
+}
+
+/// A rectangle that contains nothing, and that any point extends.
+fn empty_rect() -> ImPlotRect {
+    let mut rect = ImPlotRect::default();
+    rect.X.Min = f64::INFINITY;
+    rect.X.Max = f64::NEG_INFINITY;
+    rect.Y.Min = f64::INFINITY;
+    rect.Y.Max = f64::NEG_INFINITY;
+    rect
+}
+
+/// Start tracking the extents of plotted data for the plot that is being begun.
+pub(crate) fn begin_tracking() {
+    // BeginPlot selects the first axes
+    CURRENT_AXES.with(|axes| axes.set((0, 0)));
+    CURRENT_EXTENTS.with(|current| *current.borrow_mut() = Some(AxisExtents::empty()));
+}
+
+/// Note that data is plotted against the given axis from now on, see [`crate::set_axis`].
+pub(crate) fn select_axis(axis: Axis) {
+    CURRENT_AXES.with(|axes| {
+        let (x_index, y_index) = axes.get();
+        axes.set((
+            get_x_axis_index(axis).unwrap_or(x_index),
+            get_y_axis_index(axis).unwrap_or(y_index),
+        ));
+    });
+}
+
+/// Whether the given rectangle contains at least one point, i.e. was extended by `track_points`.
+pub(crate) fn is_nonempty(rect: &ImPlotRect) -> bool {
+    rect.X.Min <= rect.X.Max && rect.Y.Min <= rect.Y.Max
+}
+
+/// The smallest rectangle containing both given rectangles.
+pub(crate) fn union(a: &ImPlotRect, b: &ImPlotRect) -> ImPlotRect {
+    let mut rect = *a;
+    rect.X.Min = rect.X.Min.min(b.X.Min);
+    rect.X.Max = rect.X.Max.max(b.X.Max);
+    rect.Y.Min = rect.Y.Min.min(b.Y.Min);
+    rect.Y.Max = rect.Y.Max.max(b.Y.Max);
+    rect
+}
+
+/// Get the extents tracked for the current plot so far, or `None` if it does not track its
+/// extents or nothing with finite coordinates was plotted yet.
+pub(crate) fn current_extents() -> Option<AxisExtents> {
+    CURRENT_EXTENTS
+        .with(|current| *current.borrow())
+        .filter(AxisExtents::is_nonempty)
+}
+
+/// Stop tracking and store the extents tracked since [`begin_tracking`] under the given plot
+/// title. Returns the extents, or `None` if nothing with finite coordinates was plotted.
+pub(crate) fn end_tracking(title: &CString) -> Option<AxisExtents> {
+    let extents = CURRENT_EXTENTS
+        .with(|current| current.borrow_mut().take())
+        .filter(AxisExtents::is_nonempty);
+    PREVIOUS_EXTENTS.with(|previous| match extents {
+        Some(extents) => previous.borrow_mut().insert(title.clone(), extents),
+        None => previous.borrow_mut().remove(title),
+    });
+    extents
+}
+
+/// Get the extents the plot with the given title had on the most recent frame it was tracked.
+pub(crate) fn previous_extents(title: &CString) -> Option<AxisExtents> {
+    PREVIOUS_EXTENTS.with(|previous| previous.borrow().get(title).copied())
+}
+
+/// Whether plotted points are looked at, because the current plot tracks the extents of its
+/// data or the bounds of its items, or because calls are being recorded.
+pub(crate) fn wants_points() -> bool {
+    CURRENT_EXTENTS.with(|current| current.borrow().is_some())
+        || crate::items::tracks_bounds()
+        || crate::items::records_calls()
+}
+
+/// Report plotted points to the extents of the current plot and the bounds of the item that is
+/// being plotted, and to the call that is being recorded. This is a no-op if none of these are
+/// tracked. Points with non-finite coordinates are ignored, except for recording.
+pub(crate) fn track_points(x: &[f64], y: &[f64]) {
+    if !wants_points() {
+        return;
+    }
+    if crate::items::records_calls() {
+        crate::items::track_call_points(x, y);
+    }
+
+    let mut bounds = empty_rect();
+    x.iter()
+        .zip(y.iter())
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .for_each(|(x, y)| {
+            bounds.X.Min = bounds.X.Min.min(*x);
+            bounds.X.Max = bounds.X.Max.max(*x);
+            bounds.Y.Min = bounds.Y.Min.min(*y);
+            bounds.Y.Max = bounds.Y.Max.max(*y);
+        });
+    CURRENT_EXTENTS.with(|current| {
+        if let Some(extents) = current.borrow_mut().as_mut() {
+            let (x_index, y_index) = CURRENT_AXES.with(Cell::get);
+            extents.extend(x_index, y_index, &bounds);
+        }
+    });
+    if crate::items::tracks_bounds() {
+        crate::items::track_bounds(&bounds);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extents_per_axis() {
+        begin_tracking();
+        track_points(&[0.0, 1.0], &[10.0, 20.0]);
+        select_axis(Axis::Y2);
+        track_points(&[-5.0, 2.0], &[1000.0, 2000.0]);
+        let extents = end_tracking(&CString::new("Per axis").unwrap()).unwrap();
+
+        let first = extents.first_axes().unwrap();
+        assert_eq!((first.x.Min, first.x.Max), (-5.0, 2.0));
+        // The data on the second Y axis does not widen the first one
+        assert_eq!((first.y.Min, first.y.Max), (10.0, 20.0));
+        let axes: Vec<Axis> = extents
+            .axis_ranges()
+            .iter()
+            .map(|(axis, _)| *axis)
+            .collect();
+        assert_eq!(axes, vec![Axis::X1, Axis::Y1, Axis::Y2]);
+    }
+}
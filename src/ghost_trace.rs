@@ -0,0 +1,134 @@
+//! # Ghost trace module
+//!
+//! This module defines [`GhostTrace`], a helper for drawing a line together with faded copies of
+//! the data it showed on previous frames, like the persistence of an oscilloscope display.
+use crate::{sys, ImVec4, PlotLine, IMPLOT_AUTO};
+use std::{collections::VecDeque, ffi::CString};
+
+/// Draws a line of the current data behind which the data of the last few frames is drawn again
+/// as "ghosts" that fade out with their age, for seeing how a signal changes from frame to frame.
+/// Only the current line gets a legend entry. The ghosts are drawn in the color of the line, with
+/// an alpha decreasing in equal steps from the newest to the oldest one, and they are not taken
+/// into account when fitting the plot to its data.
+///
+/// Keep this around across frames, and use it like this:
+/// ```no_run
+/// # use implot::{GhostTrace, Plot, PlotUi};
+/// # fn draw(plot_ui: &PlotUi, trace: &mut GhostTrace, x: &[f64], y: &[f64]) {
+/// // Created once with GhostTrace::new("Signal", 5)
+/// Plot::new("Scope").build(plot_ui, || {
+///     trace.plot(x, y);
+/// });
+/// # }
+/// ```
+///
+/// Without an explicit color, the line gets the next color of the colormap like other items, and
+/// its ghosts take the color the line had on the previous frame.
+#[derive(Debug, Clone)]
+pub struct GhostTrace {
+    /// Label of the current line, shown in the legend
+    label: String,
+    /// Data of previous frames as X and Y values, newest first
+    history: VecDeque<(Vec<f64>, Vec<f64>)>,
+    /// Maximum number of previous frames that are kept and drawn
+    max_ghosts: usize,
+    /// Color of the line and its ghosts, if set explicitly
+    color: Option<ImVec4>,
+    /// Color ImPlot resolved for the line on the previous frame
+    last_color: Option<ImVec4>,
+}
+
+impl GhostTrace {
+    /// Create a trace with the given label that draws the data of up to `ghosts` previous
+    /// frames behind the current data.
+    pub fn new(label: &str, ghosts: usize) -> Self {
+        Self {
+            label: label.to_string(),
+            history: VecDeque::with_capacity(ghosts),
+            max_ghosts: ghosts,
+            color: None,
+            last_color: None,
+        }
+    }
+
+    /// Set the color of the line and its ghosts, instead of taking the next colormap color.
+    pub fn with_color(mut self, color: ImVec4) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Forget the data of previous frames, for example when the data source changes.
+    pub fn clear(&mut self) {
+        self.history.clear();
+    }
+
+    /// Draw the ghosts of the data of previous frames and the current data on top of them, then
+    /// keep the current data for the next frames. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build).
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotLine"))]
+    pub fn plot(&mut self, x: &[f64], y: &[f64]) {
+        if let Some(color) = self.color.or(self.last_color) {
+            let ghost_count = self.history.len();
+            let flags = sys::ImPlotItemFlags__ImPlotItemFlags_NoLegend
+                | sys::ImPlotItemFlags__ImPlotItemFlags_NoFit;
+            // Oldest first, so newer ghosts are drawn on top of older ones
+            for (age, (ghost_x, ghost_y)) in self.history.iter().enumerate().rev() {
+                let number_of_points = ghost_x.len().min(ghost_y.len());
+                if number_of_points == 0 {
+                    continue;
+                }
+                let alpha = 1.0 - (age + 1) as f32 / (ghost_count + 1) as f32;
+                let ghost_color = ImVec4 {
+                    w: color.w * alpha,
+                    ..color
+                };
+                let ghost_label = CString::new(format!("##{} ghost {}", self.label, age))
+                    .unwrap_or_else(|_| {
+                        panic!("Label string has internal null bytes: {}", self.label)
+                    });
+                unsafe {
+                    sys::ImPlot_SetNextLineStyle(ghost_color, IMPLOT_AUTO as f32);
+                    sys::ImPlot_PlotLine_doublePtrdoublePtr(
+                        ghost_label.as_ptr(),
+                        ghost_x.as_ptr(),
+                        ghost_y.as_ptr(),
+                        number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                        flags,
+                        0,                                 // No offset
+                        std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+                    );
+                }
+            }
+        }
+
+        if let Some(color) = self.color {
+            unsafe { sys::ImPlot_SetNextLineStyle(color, IMPLOT_AUTO as f32) };
+        }
+        PlotLine::new(&self.label).plot(x, y);
+        // Empty data is not plotted, and the last item is another one then
+        if !x.is_empty() && !y.is_empty() {
+            let mut line_color = ImVec4 { x: 0.0, y: 0.0, z: 0.0, w: 0.0 };
+            unsafe { sys::ImPlot_GetLastItemColor(&mut line_color as *mut ImVec4) };
+            self.last_color = Some(line_color);
+        }
+
+        if self.max_ghosts == 0 {
+            return;
+        }
+        // Reuse the buffers of the oldest ghost once there are enough of them
+        let (mut new_x, mut new_y) = if self.history.len() >= self.max_ghosts {
+            self.history.pop_back().unwrap_or_default()
+        } else {
+            Default::default()
+        };
+        self.history.truncate(self.max_ghosts - 1);
+        new_x.clear();
+        new_x.extend_from_slice(x);
+        new_y.clear();
+        new_y.extend_from_slice(y);
+        self.history.push_front((new_x, new_y));
+    }
+}
@@ -0,0 +1,232 @@
+//! # Items module
+//!
+//! This module records the items plotted into the current plot, together with the colors ImPlot
+//! resolved for them, so they can be listed after plotting, for example to draw a legend outside
+//! of the plot. Plot elements report every item they add here right after adding it. For plots
+//! that opted into it, the bounds of the data of each item are recorded as well, from the points
+//! reported to `extents::track_points` since the previous item. Plots with a limit on their
+//! number of legend entries also get the labels of their items from here, see `legend_label`.
+//! While a [`crate::PlotRecorder`] records, every item is also added to its recording, together
+//! with the points reported for it.
+use crate::{extents::DataExtents, sys, ImPlotRect, ImVec4, RecordedCall};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    ffi::{CStr, CString},
+};
+
+/// Description of an item plotted into a plot, see [`crate::PlotToken::item_info`].
+#[derive(Debug, Clone)]
+pub struct ItemInfo {
+    /// Label of the item as given to the plot element, including any hidden `##` part
+    pub label: String,
+    /// Name of the plot element type the item was plotted with, for example "PlotLine"
+    pub element_type: &'static str,
+    /// Number of data points plotted for the item. For heatmaps, this is the number of cells.
+    pub point_count: usize,
+    /// Bounding box of the item's data in plot coordinates, or `None` if the plot does not
+    /// record item bounds or the item had no points with finite coordinates
+    pub bounds: Option<DataExtents>,
+    /// Color ImPlot resolved for the item
+    pub color: ImVec4,
+}
+
+/// An item recorded for the current plot
+struct RecordedItem {
+    label: CString,
+    element_type: &'static str,
+    point_count: usize,
+    bounds: Option<ImPlotRect>,
+    color: ImVec4,
+}
+
+thread_local! {
+    /// Items plotted into the current plot so far, in the order they were first plotted. ImGui
+    /// and ImPlot are single-threaded and plots cannot be nested, so one list is enough.
+    static CURRENT_ITEMS: RefCell<Vec<RecordedItem>> = const { RefCell::new(Vec::new()) };
+
+    /// Whether the bounds of the items of the current plot are recorded
+    static TRACKS_BOUNDS: Cell<bool> = const { Cell::new(false) };
+
+    /// Bounds of the points reported since the most recent item was recorded
+    static PENDING_BOUNDS: RefCell<Option<ImPlotRect>> = const { RefCell::new(None) };
+
+    /// Maximum number of legend entries of the current plot, if it is limited
+    static LEGEND_LIMIT: Cell<Option<usize>> = const { Cell::new(None) };
+
+    /// Labels of the current plot's items that got a legend entry, and of those that were hidden
+    /// from the legend because the limit was reached
+    static LEGEND_LABELS: RefCell<(Vec<CString>, Vec<CString>)> =
+        const { RefCell::new((Vec::new(), Vec::new())) };
+
+    /// Calls recorded by the active `PlotRecorder`, if one is recording
+    static CALL_RECORDING: RefCell<Option<Vec<RecordedCall>>> = const { RefCell::new(None) };
+
+    /// Points reported since the most recent item was recorded, while a recorder is recording
+    static PENDING_POINTS: RefCell<Vec<[f64; 2]>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Start recording the items of the plot that is being begun, forgetting the previous plot's.
+/// If `track_bounds` is set, the bounds of each item's data are recorded as well. If
+/// `max_legend_entries` is set, items past that number are hidden from the legend.
+pub(crate) fn begin_recording(track_bounds: bool, max_legend_entries: Option<usize>) {
+    CURRENT_ITEMS.with(|items| items.borrow_mut().clear());
+    TRACKS_BOUNDS.with(|tracks| tracks.set(track_bounds));
+    PENDING_BOUNDS.with(|pending| *pending.borrow_mut() = None);
+    LEGEND_LIMIT.with(|limit| limit.set(max_legend_entries));
+    LEGEND_LABELS.with(|labels| *labels.borrow_mut() = (Vec::new(), Vec::new()));
+}
+
+/// Get the label to pass to ImPlot for an item with the given label. If the current plot limits
+/// its number of legend entries and the limit was already reached by other items, the label is
+/// prefixed with `##`, which keeps ImPlot from adding a legend entry for it. Labels that start
+/// with `##` already do not count towards the limit.
+pub(crate) fn legend_label(label: &CString) -> Cow<'_, CStr> {
+    let limit = match LEGEND_LIMIT.with(Cell::get) {
+        Some(limit) if !label.as_bytes().starts_with(b"##") => limit,
+        _ => return Cow::Borrowed(label.as_c_str()),
+    };
+    LEGEND_LABELS.with(|labels| {
+        let (shown, hidden) = &mut *labels.borrow_mut();
+        if !hidden.contains(label) && (shown.contains(label) || shown.len() < limit) {
+            if !shown.contains(label) {
+                shown.push(label.clone());
+            }
+            return Cow::Borrowed(label.as_c_str());
+        }
+        if !hidden.contains(label) {
+            hidden.push(label.clone());
+        }
+        let mut hidden_label = b"##".to_vec();
+        hidden_label.extend_from_slice(label.as_bytes());
+        // The label came from a CString, so it has no internal null bytes
+        Cow::Owned(CString::new(hidden_label).unwrap())
+    })
+}
+
+/// Get the number of items of the current plot that were hidden from the legend so far because
+/// its limit on legend entries was reached.
+pub(crate) fn hidden_legend_entries() -> usize {
+    LEGEND_LABELS.with(|labels| labels.borrow().1.len())
+}
+
+/// Whether the bounds of the items of the current plot are recorded.
+pub(crate) fn tracks_bounds() -> bool {
+    TRACKS_BOUNDS.with(Cell::get)
+}
+
+/// Add the bounds of plotted points to the bounds of the item that is about to be recorded.
+pub(crate) fn track_bounds(bounds: &ImPlotRect) {
+    PENDING_BOUNDS.with(|pending| {
+        let mut pending = pending.borrow_mut();
+        *pending = Some(match pending.take() {
+            Some(rect) => crate::extents::union(&rect, bounds),
+            None => *bounds,
+        });
+    });
+}
+
+/// Forget the bounds of points that were reported for something that is not recorded as an
+/// item, such as text, so they do not end up in the bounds of the next item.
+pub(crate) fn discard_pending_bounds() {
+    PENDING_BOUNDS.with(|pending| *pending.borrow_mut() = None);
+    PENDING_POINTS.with(|pending| pending.borrow_mut().clear());
+}
+
+/// Start recording calls, returning the calls of the recording that was active before, if any,
+/// which has to be passed to `end_call_recording` again.
+pub(crate) fn begin_call_recording() -> Option<Vec<RecordedCall>> {
+    PENDING_POINTS.with(|pending| pending.borrow_mut().clear());
+    CALL_RECORDING.with(|recording| recording.borrow_mut().replace(Vec::new()))
+}
+
+/// Stop recording calls and return the recorded ones, making the recording that was active
+/// before the current one was begun active again.
+pub(crate) fn end_call_recording(previous: Option<Vec<RecordedCall>>) -> Vec<RecordedCall> {
+    PENDING_POINTS.with(|pending| pending.borrow_mut().clear());
+    CALL_RECORDING
+        .with(|recording| std::mem::replace(&mut *recording.borrow_mut(), previous))
+        .unwrap_or_default()
+}
+
+/// Whether calls are being recorded.
+pub(crate) fn records_calls() -> bool {
+    CALL_RECORDING.with(|recording| recording.borrow().is_some())
+}
+
+/// Add plotted points to the points of the call that is about to be recorded.
+pub(crate) fn track_call_points(x: &[f64], y: &[f64]) {
+    PENDING_POINTS.with(|pending| {
+        pending
+            .borrow_mut()
+            .extend(x.iter().zip(y.iter()).map(|(x, y)| [*x, *y]))
+    });
+}
+
+/// Record the item ImPlot added last, under the given label, with the plot element type it was
+/// plotted with and the number of points plotted for it. Items with the same label are the same
+/// item in ImPlot, so plotting into an item again adds to its point count and bounds.
+pub(crate) fn record_last_item(label: &CString, element_type: &'static str, point_count: usize) {
+    let mut color = ImVec4 { x: 0.0, y: 0.0, z: 0.0, w: 0.0 };
+    unsafe { sys::ImPlot_GetLastItemColor(&mut color as *mut ImVec4) };
+    let bounds = PENDING_BOUNDS
+        .with(|pending| pending.borrow_mut().take())
+        .filter(crate::extents::is_nonempty);
+    CALL_RECORDING.with(|recording| {
+        if let Some(calls) = recording.borrow_mut().as_mut() {
+            calls.push(RecordedCall {
+                label: label.to_string_lossy().into_owned(),
+                element_type: element_type.to_string(),
+                point_count,
+                points: PENDING_POINTS.with(|pending| std::mem::take(&mut *pending.borrow_mut())),
+            });
+        }
+    });
+    CURRENT_ITEMS.with(|items| {
+        let mut items = items.borrow_mut();
+        match items.iter_mut().find(|item| item.label == *label) {
+            Some(item) => {
+                item.point_count += point_count;
+                item.bounds = match (item.bounds, bounds) {
+                    (Some(a), Some(b)) => Some(crate::extents::union(&a, &b)),
+                    (a, b) => a.or(b),
+                };
+            }
+            None => items.push(RecordedItem {
+                label: label.clone(),
+                element_type,
+                point_count,
+                bounds,
+                color,
+            }),
+        }
+    });
+}
+
+/// Get the items recorded for the current plot so far, as full labels and colors.
+pub(crate) fn recorded_items() -> Vec<(CString, ImVec4)> {
+    CURRENT_ITEMS.with(|items| {
+        items
+            .borrow()
+            .iter()
+            .map(|item| (item.label.clone(), item.color))
+            .collect()
+    })
+}
+
+/// Get descriptions of the items recorded for the current plot so far.
+pub(crate) fn recorded_item_info() -> Vec<ItemInfo> {
+    CURRENT_ITEMS.with(|items| {
+        items
+            .borrow()
+            .iter()
+            .map(|item| ItemInfo {
+                label: item.label.to_string_lossy().into_owned(),
+                element_type: item.element_type,
+                point_count: item.point_count,
+                bounds: item.bounds.map(DataExtents::from),
+                color: item.color,
+            })
+            .collect()
+    })
+}
@@ -19,16 +19,42 @@
 use implot_sys as sys;
 
 // TODO(4bb4) facade-wrap these?
-pub use self::{context::*, plot::*, plot_elements::*};
+pub use self::{
+    auto_scroll::AutoScrollX,
+    context::*,
+    custom_elements::*,
+    extents::DataExtents,
+    ghost_trace::GhostTrace,
+    items::ItemInfo,
+    plot::*,
+    plot_elements::*,
+    quantity::Quantity,
+    recorder::{PlotRecorder, PlotRecording, RecordedCall},
+    shared_cursor::SharedCursorX,
+};
 use std::{
+    cell::RefCell,
+    collections::HashMap,
+    ffi::CString,
     mem::MaybeUninit,
-    os::raw::c_char
+    os::raw::c_char,
 };
 pub use sys::{ImPlotRect, ImPlotPoint, ImPlotRange, ImVec2, ImVec4};
 
+mod auto_scroll;
+mod callback;
 mod context;
+mod custom_elements;
+mod extents;
+mod ghost_trace;
+mod items;
 mod plot;
 mod plot_elements;
+mod plot_state;
+mod quantity;
+mod recorder;
+mod scratch;
+mod shared_cursor;
 
 // The bindings for some reason don't contain this - it has to match the IMPLOT_AUTO from
 // the original C++ header for things to work properly.
@@ -107,6 +133,35 @@ pub struct PlotUi<'ui> {
     context: &'ui Context,
 }
 
+impl PlotUi<'_> {
+    /// Panic if a plot was begun and not ended, or if style colors, style variables or colormaps
+    /// were pushed and not popped. Call this after all plotting of a frame is done, to catch a
+    /// forgotten `end()` or `pop()` at a known point instead of depending on when tokens are
+    /// dropped. ImPlot does not expose its nesting state through its public API, so this checks
+    /// what was begun and pushed through this crate since the context was created; calls made
+    /// directly through `implot-sys` are not seen.
+    pub fn assert_balanced(&self) {
+        let counts = STACK_COUNTS.with(|counts| *counts.borrow());
+        let open = [
+            ("plots", counts.plots),
+            ("style colors", counts.style_colors),
+            ("style variables", counts.style_vars),
+            ("colormaps", counts.colormaps),
+        ]
+        .iter()
+        .filter_map(|(name, count)| match *count {
+            0 => None,
+            count => Some(format!("{} {}", count, name)),
+        })
+        .collect::<Vec<String>>();
+        assert!(
+            open.is_empty(),
+            "ImPlot state is unbalanced, still open or pushed: {}",
+            open.join(", ")
+        );
+    }
+}
+
 // --- Markers, color maps, style variables, legend location ----------------------------------
 /// Markers, documentation copied from implot.h for convenience.
 #[rustversion::attr(since(1.48), doc(alias = "ImPlotMarker"))]
@@ -227,6 +282,17 @@ pub enum Colormap {
     Greys = sys::ImPlotColormap__ImPlotColormap_Greys as u32,
 }
 
+impl Colormap {
+    /// Whether this is a qualitative colormap, meant for telling categories apart, as opposed to
+    /// a sequential or diverging one meant for showing magnitudes.
+    pub fn is_qualitative(self) -> bool {
+        matches!(
+            self,
+            Colormap::Deep | Colormap::Dark | Colormap::Pastel | Colormap::Paired
+        )
+    }
+}
+
 /// Style variable choice, as in "which thing will be affected by a style setting".
 #[rustversion::attr(since(1.48), doc(alias = "ImPlotStyleVar"))]
 #[repr(u32)]
@@ -314,6 +380,40 @@ pub enum PlotLocation {
     SouthEast = sys::ImPlotLocation__ImPlotLocation_SouthEast as u32,
 }
 
+/// Scale of an axis, which determines how plot coordinates are mapped to positions along it
+#[rustversion::attr(since(1.48), doc(alias = "ImPlotScale"))]
+#[repr(u32)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AxisScale {
+    /// Default linear scale
+    Linear = sys::ImPlotScale__ImPlotScale_Linear as u32,
+    /// Date/time scale, with values being UNIX timestamps in seconds
+    Time = sys::ImPlotScale__ImPlotScale_Time as u32,
+    /// Base 10 logarithmic scale
+    Log10 = sys::ImPlotScale__ImPlotScale_Log10 as u32,
+    /// Symmetric logarithmic scale, which is linear close to zero
+    SymLog = sys::ImPlotScale__ImPlotScale_SymLog as u32,
+}
+
+/// Side of the plot an X axis is displayed on
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum XAxisSide {
+    /// Below the plot area, the default for all X axes
+    Bottom,
+    /// Above the plot area
+    Top,
+}
+
+/// Side of the plot a Y axis is displayed on
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum YAxisSide {
+    /// Left of the plot area, the default for all Y axes
+    Left,
+    /// Right of the plot area
+    Right,
+}
+
 /// Switch to one of the built-in preset colormaps. If samples is greater than 1, the map will be
 /// linearly resampled.
 #[rustversion::attr(since(1.48), doc(alias = "SetColormap"))]
@@ -334,9 +434,219 @@ pub fn set_colormap_from_vec(colors: Vec<ImVec4>) {
     }
 }
 
+/// Switch to a reversed version of one of the built-in preset colormaps, for example viridis
+/// with dark colors for high values. If samples is greater than 1, the map is linearly resampled
+/// to that many colors. ImPlot has no option to reverse colormaps, so the reversed colors are
+/// added to ImPlot as a new colormap named after the preset, such as "Viridis reversed" (or
+/// "Viridis reversed 16" when resampled). This happens on the first call only, later calls reuse
+/// the colormap that was added then.
+#[rustversion::attr(since(1.48), doc(alias = "AddColormap"))]
+pub fn set_colormap_reversed(preset: Colormap, samples: u32) {
+    let name = if samples > 1 {
+        format!("{:?} reversed {}", preset, samples)
+    } else {
+        format!("{:?} reversed", preset)
+    };
+    // Preset names have no null bytes
+    let name = CString::new(name).unwrap();
+    let mut index = unsafe { sys::ImPlot_GetColormapIndex(name.as_ptr()) };
+    if index == IMPLOT_AUTO {
+        let mut colors: Vec<ImVec4> = if samples > 1 {
+            (0..samples)
+                .map(|sample| {
+                    let mut color = ImVec4 { x: 0.0, y: 0.0, z: 0.0, w: 0.0 };
+                    let t = sample as f32 / (samples - 1) as f32;
+                    unsafe {
+                        sys::ImPlot_SampleColormap(&mut color as *mut ImVec4, t, preset as i32)
+                    };
+                    color
+                })
+                .collect()
+        } else {
+            colormap_colors(Some(preset))
+        };
+        colors.reverse();
+        index = unsafe {
+            sys::ImPlot_AddColormap_Vec4Ptr(
+                name.as_ptr(),
+                colors.as_ptr(),
+                colors.len() as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                preset.is_qualitative(),
+            )
+        };
+    }
+    unsafe { (*sys::ImPlot_GetStyle()).Colormap = index };
+}
+
+/// Get all colors of a colormap, in order. If `None` is given, the currently active colormap
+/// is used. This returns the exact discrete entries of the colormap, as opposed to sampling it.
+#[rustversion::attr(since(1.48), doc(alias = "GetColormapColor"))]
+#[rustversion::attr(since(1.48), doc(alias = "GetColormapSize"))]
+pub fn colormap_colors(colormap: Option<Colormap>) -> Vec<ImVec4> {
+    let colormap = colormap.map_or(IMPLOT_AUTO, |v| v as i32);
+    let size = unsafe { sys::ImPlot_GetColormapSize(colormap) };
+    (0..size)
+        .map(|index| {
+            let mut color = ImVec4 { x: 0.0, y: 0.0, z: 0.0, w: 0.0 };
+            unsafe {
+                sys::ImPlot_GetColormapColor(&mut color as *mut ImVec4, index, colormap);
+            }
+            color
+        })
+        .collect()
+}
+
+/// Draw a colorbar showing how the values from `scale_min` to `scale_max` map to the colors of
+/// a colormap, for example next to a heatmap. If `None` is given as the colormap, the currently
+/// active one is used. A size of zero in either direction uses a default size there. This is a
+/// standalone widget, so call it outside of plots.
+///
+/// # Panics
+/// Will panic if the label string contains internal null bytes.
+#[rustversion::attr(since(1.48), doc(alias = "ColormapScale"))]
+pub fn colormap_scale(
+    label: &str,
+    scale_min: f64,
+    scale_max: f64,
+    size: [f32; 2],
+    colormap: Option<Colormap>,
+) {
+    let label = CString::new(label)
+        .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label));
+    unsafe {
+        sys::ImPlot_ColormapScale(
+            label.as_ptr(),
+            scale_min,
+            scale_max,
+            ImVec2 { x: size[0], y: size[1] },
+            b"%g\0".as_ptr() as *const c_char,
+            0,
+            colormap.map_or(IMPLOT_AUTO, |v| v as i32),
+        );
+    }
+}
+
 // --- Push/pop utils -------------------------------------------------------------------------
 // Currently not in a struct yet. imgui-rs has some smarts about dealing with stacks, in particular
 // leak detection, which I'd like to replicate here at some point.
+
+/// Number of entries pushed to the ImPlot stacks through this crate and not popped yet, and of
+/// plots begun and not ended yet. ImPlot does not expose its stack sizes through the public API,
+/// so they are tracked here instead. The stacks belong to the ImPlot context, which is used from
+/// one thread, so this is kept per thread and reset whenever a context is created or dropped.
+#[derive(Copy, Clone, Debug, Default)]
+struct StackCounts {
+    style_colors: usize,
+    style_vars: usize,
+    colormaps: usize,
+    plots: usize,
+    /// Number of times the style stacks were cleared with [`force_clear_style_stacks`] or
+    /// discarded with their context. Tokens remember the generation they were pushed in, so
+    /// tokens from before a clear do not pop newer entries.
+    generation: usize,
+}
+
+/// The stacks tracked in [`StackCounts`].
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum Stack {
+    StyleColors,
+    StyleVars,
+    Colormaps,
+    Plots,
+}
+
+impl StackCounts {
+    fn count_mut(&mut self, stack: Stack) -> &mut usize {
+        match stack {
+            Stack::StyleColors => &mut self.style_colors,
+            Stack::StyleVars => &mut self.style_vars,
+            Stack::Colormaps => &mut self.colormaps,
+            Stack::Plots => &mut self.plots,
+        }
+    }
+}
+
+thread_local! {
+    static STACK_COUNTS: RefCell<StackCounts> = RefCell::new(StackCounts::default());
+}
+
+/// Forget the entries counted on the stacks, for a context that was just created or dropped.
+/// The generation is advanced rather than reset, so tokens from the previous context stay stale.
+pub(crate) fn reset_stack_counts() {
+    STACK_COUNTS.with(|counts| {
+        let mut counts = counts.borrow_mut();
+        *counts = StackCounts {
+            generation: counts.generation + 1,
+            ..StackCounts::default()
+        };
+    });
+}
+
+/// Count an entry pushed to one of the stacks, returning the generation it was pushed in.
+pub(crate) fn note_pushed(stack: Stack) -> usize {
+    STACK_COUNTS.with(|counts| {
+        let mut counts = counts.borrow_mut();
+        *counts.count_mut(stack) += 1;
+        counts.generation
+    })
+}
+
+/// Count an entry popped from one of the stacks, returning whether there was anything left to
+/// pop.
+pub(crate) fn take_pushed(stack: Stack) -> bool {
+    STACK_COUNTS.with(|counts| {
+        let mut counts = counts.borrow_mut();
+        let count = counts.count_mut(stack);
+        match count.checked_sub(1) {
+            Some(remaining) => {
+                *count = remaining;
+                true
+            }
+            None => false,
+        }
+    })
+}
+
+/// Count an entry popped from one of the style stacks that was pushed in the given generation,
+/// returning whether it is still on the stack. Entries pushed before the stacks were last
+/// cleared are gone already.
+fn take_style_pushed(stack: Stack, generation: usize) -> bool {
+    let current = STACK_COUNTS.with(|counts| counts.borrow().generation);
+    generation == current && take_pushed(stack)
+}
+
+/// Forget the entries on the style stacks and start a new generation, returning the counts of
+/// the entries that have to be popped.
+fn clear_style_counts() -> StackCounts {
+    STACK_COUNTS.with(|counts| {
+        let mut counts = counts.borrow_mut();
+        let cleared = *counts;
+        counts.style_colors = 0;
+        counts.style_vars = 0;
+        counts.colormaps = 0;
+        counts.generation += 1;
+        cleared
+    })
+}
+
+/// Pop everything that was pushed to the style color, style variable and colormap stacks through
+/// this crate and has not been popped yet.
+///
+/// This is meant for test teardown: when a test fails between a push and the corresponding
+/// `pop()`, the stacks would otherwise stay dirty for all subsequent tests. Calling this
+/// explicitly marks the cleanup as intentional. Tokens that were still outstanding become no-ops
+/// when popped afterwards, even if new entries were pushed in the meantime, so do not use this to
+/// paper over forgotten `pop()` calls in regular code.
+pub fn force_clear_style_stacks() {
+    let cleared = clear_style_counts();
+    // "as" casts saturate as of Rust 1.45. This is safe here.
+    unsafe {
+        sys::ImPlot_PopStyleColor(cleared.style_colors as i32);
+        sys::ImPlot_PopStyleVar(cleared.style_vars as i32);
+        sys::ImPlot_PopColormap(cleared.colormaps as i32);
+    }
+}
+
 /// Push a style color to the stack, giving an element and the four components of the color.
 /// The components should be between 0.0 (no intensity) and 1.0 (full intensity).
 /// The return value is a token that gets used for removing the style color from the stack again:
@@ -365,13 +675,18 @@ pub fn push_style_color(
             },
         );
     }
-    StyleColorToken { was_popped: false }
+    StyleColorToken {
+        was_popped: false,
+        generation: note_pushed(Stack::StyleColors),
+    }
 }
 
 /// Tracks a change pushed to the style color stack
 pub struct StyleColorToken {
     /// Whether this token has been popped or not.
     was_popped: bool,
+    /// How often the stacks had been cleared when this token was pushed
+    generation: usize,
 }
 
 impl StyleColorToken {
@@ -381,8 +696,11 @@ impl StyleColorToken {
             panic!("Attempted to pop a style color token twice.")
         }
         self.was_popped = true;
-        unsafe {
-            sys::ImPlot_PopStyleColor(1);
+        // Nothing to pop if the stack was cleared with force_clear_style_stacks() in the meantime
+        if take_style_pushed(Stack::StyleColors, self.generation) {
+            unsafe {
+                sys::ImPlot_PopStyleColor(1);
+            }
         }
     }
 }
@@ -400,23 +718,36 @@ pub fn push_style_var_f32(element: &StyleVar, value: f32) -> StyleVarToken {
     unsafe {
         sys::ImPlot_PushStyleVar_Float(*element as sys::ImPlotStyleVar, value);
     }
-    StyleVarToken { was_popped: false }
+    StyleVarToken {
+        was_popped: false,
+        generation: note_pushed(Stack::StyleVars),
+    }
 }
 
 /// Push an u32 style variable to the stack. The only i32 style variable is Marker
-/// at the moment, for that, use something like
-/// ```no_run
-/// # use implot::{push_style_var_i32, StyleVar, Marker};
-/// let markerchoice = push_style_var_i32(&StyleVar::Marker, Marker::Cross as i32);
-/// // plot things
-/// markerchoice.pop()
-/// ```
+/// at the moment, for which [`push_marker`] is more convenient.
 #[rustversion::attr(since(1.48), doc(alias = "PushStyleVar"))]
 pub fn push_style_var_i32(element: &StyleVar, value: i32) -> StyleVarToken {
     unsafe {
         sys::ImPlot_PushStyleVar_Int(*element as sys::ImPlotStyleVar, value);
     }
-    StyleVarToken { was_popped: false }
+    StyleVarToken {
+        was_popped: false,
+        generation: note_pushed(Stack::StyleVars),
+    }
+}
+
+/// Push a marker choice to the style variable stack, for plot elements drawn until the returned
+/// token is popped. Use it like
+/// ```no_run
+/// # use implot::{push_marker, Marker};
+/// let markerchoice = push_marker(Marker::Cross);
+/// // plot things
+/// markerchoice.pop()
+/// ```
+#[rustversion::attr(since(1.48), doc(alias = "PushStyleVar"))]
+pub fn push_marker(marker: Marker) -> StyleVarToken {
+    push_style_var_i32(&StyleVar::Marker, marker as i32)
 }
 
 /// Push an ImVec2 style variable to the stack. The returned token is used for removing
@@ -425,13 +756,18 @@ pub fn push_style_var_imvec2(element: &StyleVar, value: ImVec2) -> StyleVarToken
     unsafe {
         sys::ImPlot_PushStyleVar_Vec2(*element as sys::ImPlotStyleVar, value);
     }
-    StyleVarToken { was_popped: false }
+    StyleVarToken {
+        was_popped: false,
+        generation: note_pushed(Stack::StyleVars),
+    }
 }
 
 /// Tracks a change pushed to the style variable stack
 pub struct StyleVarToken {
     /// Whether this token has been popped or not.
     was_popped: bool,
+    /// How often the stacks had been cleared when this token was pushed
+    generation: usize,
 }
 
 impl StyleVarToken {
@@ -442,13 +778,72 @@ impl StyleVarToken {
             panic!("Attempted to pop a style var token twice.")
         }
         self.was_popped = true;
-        unsafe {
-            sys::ImPlot_PopStyleVar(1);
+        // Nothing to pop if the stack was cleared with force_clear_style_stacks() in the meantime
+        if take_style_pushed(Stack::StyleVars, self.generation) {
+            unsafe {
+                sys::ImPlot_PopStyleVar(1);
+            }
+        }
+    }
+}
+
+/// Push one of the built-in preset colormaps to the colormap stack. The returned token is used
+/// for removing the colormap from the stack again:
+/// ```no_run
+/// # use implot::{push_colormap_from_preset, Colormap};
+/// let pushed_map = push_colormap_from_preset(Colormap::Viridis);
+/// // Plot some things
+/// pushed_map.pop();
+/// ```
+#[rustversion::attr(since(1.48), doc(alias = "PushColormap"))]
+pub fn push_colormap_from_preset(preset: Colormap) -> ColormapToken {
+    unsafe {
+        sys::ImPlot_PushColormap_PlotColormap(preset as sys::ImPlotColormap);
+    }
+    ColormapToken {
+        was_popped: false,
+        generation: note_pushed(Stack::Colormaps),
+    }
+}
+
+/// Tracks a change pushed to the colormap stack
+pub struct ColormapToken {
+    /// Whether this token has been popped or not.
+    was_popped: bool,
+    /// How often the stacks had been cleared when this token was pushed
+    generation: usize,
+}
+
+impl ColormapToken {
+    /// Pop this token from the stack.
+    #[rustversion::attr(since(1.48), doc(alias = "PopColormap"))]
+    pub fn pop(mut self) {
+        if self.was_popped {
+            panic!("Attempted to pop a colormap token twice.")
+        }
+        self.was_popped = true;
+        // Nothing to pop if the stack was cleared with force_clear_style_stacks() in the meantime
+        if take_style_pushed(Stack::Colormaps, self.generation) {
+            unsafe {
+                sys::ImPlot_PopColormap(1);
+            }
         }
     }
 }
 
 // --- Miscellaneous -----------------------------------------------------------------------------
+/// Returns the version of the ImPlot library this crate was built with, for example "0.16", to
+/// include in bug reports or to check for features of newer ImPlot versions.
+pub fn implot_version() -> &'static str {
+    sys::IMPLOT_VERSION
+}
+
+/// Returns true if the current or most recent plot is the one the user interacted with most
+/// recently, by clicking or scrolling in its plot area. See [`PlotToken::is_active`].
+pub fn is_plot_active() -> bool {
+    plot_state::is_current_plot_active()
+}
+
 /// Returns true if the plot area in the current or most recent plot is hovered.
 #[rustversion::attr(since(1.48), doc(alias = "IsPlotHovered"))]
 pub fn is_plot_hovered() -> bool {
@@ -560,6 +955,80 @@ pub fn plot_to_pixels_f32(
     pixel_position
 }
 
+/// Returns how many pixels one data unit spans on the given X and Y axes of the current plot, as
+/// `(x, y)`. This is useful for drawing things with a fixed size in pixels at positions in plot
+/// coordinates. The values are signed: since pixel Y coordinates grow downwards, the Y value is
+/// usually negative, and inverted axes have the opposite sign. The result is only meaningful for
+/// linear axes. Only call this between beginning and ending a plot.
+pub fn axis_pixels_per_unit(x_axis: Axis, y_axis: Axis) -> (f64, f64) {
+    // Converting the corners of the plot limits instead of two points one unit apart keeps the
+    // f32 pixel coordinates from losing precision when the limits are far from the origin.
+    let limits = get_plot_limits(x_axis, y_axis);
+    let start = plot_to_pixels_f32(limits.X.Min, limits.Y.Min, x_axis, y_axis);
+    let end = plot_to_pixels_f32(limits.X.Max, limits.Y.Max, x_axis, y_axis);
+    (
+        (end.x - start.x) as f64 / (limits.X.Max - limits.X.Min),
+        (end.y - start.y) as f64 / (limits.Y.Max - limits.Y.Min),
+    )
+}
+
+/// Returns the top left corner of the plot area of the current plot, in pixels. The plot area is
+/// the region inside the axes, i.e. it does not include the title, axis labels or tick labels.
+/// Only call this between beginning and ending a plot.
+#[rustversion::attr(since(1.48), doc(alias = "GetPlotPos"))]
+pub fn get_plot_position() -> ImVec2 {
+    let mut position = ImVec2 { x: 0.0, y: 0.0 }; // doesn't seem to have default()
+    unsafe {
+        sys::ImPlot_GetPlotPos(&mut position as *mut ImVec2);
+    }
+    position
+}
+
+/// Returns the size of the plot area of the current plot, in pixels. See
+/// [`get_plot_position`] for what the plot area contains. Only call this between beginning
+/// and ending a plot.
+#[rustversion::attr(since(1.48), doc(alias = "GetPlotSize"))]
+pub fn get_plot_size() -> ImVec2 {
+    let mut size = ImVec2 { x: 0.0, y: 0.0 }; // doesn't seem to have default()
+    unsafe {
+        sys::ImPlot_GetPlotSize(&mut size as *mut ImVec2);
+    }
+    size
+}
+
+/// Returns the rectangle of the plot area of the current plot as `(min, max)` pixel corners.
+/// The space taken up by the axis gutters on each side is the difference between these corners
+/// and those of [`get_plot_frame_rect`]. Only call this between beginning and ending a plot.
+pub fn get_plot_area_rect() -> (ImVec2, ImVec2) {
+    let position = get_plot_position();
+    let size = get_plot_size();
+    (
+        position,
+        ImVec2 {
+            x: position.x + size.x,
+            y: position.y + size.y,
+        },
+    )
+}
+
+/// Returns the rectangle of the whole frame of the current plot as `(min, max)` pixel corners,
+/// which contains the title, axis labels and tick labels around the plot area. ImPlot does not
+/// expose the frame through its public API, so this is the imgui item rectangle `BeginPlot`
+/// added, recorded when the plot was begun through this library. Only call this between
+/// beginning and ending a plot.
+pub fn get_plot_frame_rect() -> (ImVec2, ImVec2) {
+    crate::plot_state::current_frame()
+}
+
+/// Returns the extents of the data plotted into the plot with the given title on the most recent
+/// frame it was shown, if that plot tracks its extents (see [`Plot::with_data_extents`]). This
+/// can be used outside of the plot, for example for a "fit to data" button above it. Like
+/// [`PlotToken::data_extents`], this only covers the data plotted against the first axes.
+pub fn plot_data_extents(title: &str) -> Option<DataExtents> {
+    let title = CString::new(title).ok()?;
+    crate::extents::previous_extents(&title)?.first_axes()
+}
+
 /// Returns the current or most recent plot axis range for the specified choice of Y axis. If
 /// `None` is the Y axis choice, that means the most recently selected Y axis is chosen.
 #[rustversion::attr(since(1.48), doc(alias = "GetPlotLimits"))]
@@ -575,6 +1044,81 @@ pub fn get_plot_limits(x_axis: Axis, y_axis: Axis) -> ImPlotRect {
     limits
 }
 
+/// Returns the positions of the major ticks ImPlot generates for the given axis of the current
+/// plot, for example to align a custom grid overlay with the plot's own grid. Only call this
+/// between beginning and ending a plot.
+///
+/// ImPlot does not expose the ticks it generated, so they are computed again with
+/// [`nice_ticks`] from the current limits and size of the plot area. This matches the ticks of
+/// linear axes with default ticks. Axes with custom ticks, logarithmic or time scales or custom
+/// scales use other tick locators, which this does not reproduce.
+pub fn auto_ticks(axis: Axis) -> Vec<f64> {
+    let size = get_plot_size();
+    if get_x_axis_index(axis).is_some() {
+        let limits = get_plot_limits(axis, Axis::Y1);
+        nice_ticks(limits.X.Min, limits.X.Max, size.x, false)
+    } else {
+        let limits = get_plot_limits(Axis::X1, axis);
+        nice_ticks(limits.Y.Min, limits.Y.Max, size.y, true)
+    }
+}
+
+/// Returns the positions of the major ticks ImPlot's default tick locator places in the range
+/// from `min` to `max` on an axis that is `pixels` long, in the same order. This reimplements the
+/// algorithm of the vendored ImPlot version: about one major tick per 400 pixels horizontally
+/// and per 300 pixels vertically, but at least two, spaced by a "nice" interval of 1, 2 or 5
+/// times a power of ten. Minor ticks lie at every tenth of that interval. Ticks at positions
+/// close to zero are placed exactly at zero, like ImPlot does.
+pub fn nice_ticks(min: f64, max: f64, pixels: f32, vertical: bool) -> Vec<f64> {
+    if min >= max || !min.is_finite() || !max.is_finite() {
+        return Vec::new();
+    }
+    let pixels_per_tick = if vertical { 300.0 } else { 400.0 };
+    let major_count = ((pixels / pixels_per_tick + 0.5) as i32).max(2);
+    let nice_range = nice_number((max - min) * 0.99, false);
+    let interval = nice_number(nice_range / (major_count - 1) as f64, true);
+    let graph_min = (min / interval).floor() * interval;
+    let graph_max = (max / interval).ceil() * interval;
+
+    let mut ticks = Vec::new();
+    let mut major = graph_min;
+    while major < graph_max + 0.5 * interval {
+        // Like ImPlot, avoid ticks at tiny nonzero values caused by accumulated rounding errors
+        if major - interval < 0.0 && major + interval > 0.0 {
+            major = 0.0;
+        }
+        if min <= major && major <= max {
+            ticks.push(major);
+        }
+        major += interval;
+    }
+    ticks
+}
+
+/// Internal helper to find a "nice" number close to `x`, that is 1, 2, 5 or 10 times a power of
+/// ten. Rounds to the closest one if `round` is set, otherwise takes the next larger one. This
+/// follows `NiceNum` in ImPlot.
+fn nice_number(x: f64, round: bool) -> f64 {
+    let exponent = x.log10().floor();
+    let fraction = x / 10f64.powf(exponent);
+    let nice_fraction = if round {
+        match fraction {
+            f if f < 1.5 => 1.0,
+            f if f < 3.0 => 2.0,
+            f if f < 7.0 => 5.0,
+            _ => 10.0,
+        }
+    } else {
+        match fraction {
+            f if f <= 1.0 => 1.0,
+            f if f <= 2.0 => 2.0,
+            f if f <= 5.0 => 5.0,
+            _ => 10.0,
+        }
+    };
+    nice_fraction * 10f64.powf(exponent)
+}
+
 /// Returns the query limits of the current or most recent plot, for the specified choice of Y
 /// axis. If `None` is the Y axis choice, that means the most recently selected Y axis is chosen.
 #[rustversion::attr(since(1.48), doc(alias = "GetPlotQuery"))]
@@ -603,6 +1147,63 @@ pub fn set_axis(axis: Axis) {
     unsafe {
         sys::ImPlot_SetAxis(axis as i32);
     }
+    crate::extents::select_axis(axis);
+}
+
+/// Internal helper to select the first X and Y axes for the upcoming plot elements.
+pub(crate) fn set_first_axes() {
+    unsafe {
+        sys::ImPlot_SetAxes(Axis::X1 as i32, Axis::Y1 as i32);
+    }
+    crate::extents::select_axis(Axis::X1);
+    crate::extents::select_axis(Axis::Y1);
+}
+
+/// Plot the elements plotted in `f` against the given X or Y axis, and switch back to the first
+/// X or Y axis afterwards, so elements plotted later are not accidentally plotted against the
+/// given axis as well. Only call this between beginning and ending a plot.
+pub fn plot_on_axis<F: FnOnce()>(axis: Axis, f: F) {
+    set_axis(axis);
+    f();
+    if get_x_axis_index(axis).is_some() {
+        set_axis(Axis::X1);
+    } else {
+        set_axis(Axis::Y1);
+    }
+}
+
+/// Amount of geometry plot elements added to the plot's draw list, see [`measure_draw_load`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DrawLoad {
+    /// Number of vertices added
+    pub vertices: usize,
+    /// Number of indices added, three per triangle
+    pub indices: usize,
+}
+
+/// Run `f` and measure how much geometry the plot elements plotted in it added to the draw list
+/// of the current plot, for checking whether optimizations for large data, such as plotting
+/// fewer points, reduce the draw load. Only call this between beginning and ending a plot.
+///
+/// ImPlot does not count how many points are left after clipping, so this is the closest
+/// measure available. ImPlot leaves out the geometry of segments and markers that are entirely
+/// outside of the plot area, so zooming in reduces the load. Each drawn segment of a line takes
+/// 4 vertices and 6 indices, so `indices / 6` of a line is its number of drawn segments, compared
+/// to one less than the number of points submitted. Markers, fills and the items of other
+/// elements take different amounts depending on their shape. Drawing that does not go through
+/// the plot's draw list, such as the legend, is not measured.
+pub fn measure_draw_load<F: FnOnce()>(f: F) -> DrawLoad {
+    let buffer_sizes = || unsafe {
+        let draw_list = &*sys::ImPlot_GetPlotDrawList();
+        (draw_list.VtxBuffer.Size, draw_list.IdxBuffer.Size)
+    };
+    let (vertices_before, indices_before) = buffer_sizes();
+    f();
+    let (vertices_after, indices_after) = buffer_sizes();
+    DrawLoad {
+        vertices: vertices_after.saturating_sub(vertices_before) as usize,
+        indices: indices_after.saturating_sub(indices_before) as usize,
+    }
 }
 
 /// Returns true if the axis area of the given axis choice in the current plot is hovered. If
@@ -613,11 +1214,178 @@ pub fn is_plot_axis_hovered(axis_choice: Option<Axis>) -> bool {
     unsafe { sys::ImPlot_IsAxisHovered(axis_choice_i32) }
 }
 
+/// Returns the axis of the current plot whose axis area is hovered, if any. All X and Y axes are
+/// checked, in the order X1, X2, X3, Y1, Y2, Y3, and the first hovered one is returned.
+#[rustversion::attr(since(1.48), doc(alias = "IsPlotAxisHovered"))]
+pub fn hovered_axis() -> Option<Axis> {
+    (0..NUMBER_OF_X_AXES)
+        .filter_map(get_x_axis_from_index)
+        .chain((0..NUMBER_OF_Y_AXES).filter_map(get_y_axis_from_index))
+        .find(|axis| is_plot_axis_hovered(Some(*axis)))
+}
+
+/// Returns the colors of the items plotted into the current plot so far, keyed by the labels
+/// they were plotted with (including any hidden `##` part). This also covers items whose color
+/// ImPlot picked from the colormap automatically. ImPlot does not expose which colormap entry it
+/// will use next, so the colors are read back from each item right after it was plotted, which
+/// means only items plotted through this crate's plot elements are included. Only call this
+/// between beginning and ending a plot; see also [`PlotToken::items`].
+#[rustversion::attr(since(1.48), doc(alias = "GetLastItemColor"))]
+pub fn plotted_item_colors() -> HashMap<String, ImVec4> {
+    items::recorded_items()
+        .into_iter()
+        .map(|(label, color)| (label.to_string_lossy().into_owned(), color))
+        .collect()
+}
+
+/// Draw a legend as a standalone imgui widget, with a colored swatch and the label of each item,
+/// laid out in rows that wrap at `wrap_width` pixels. If `wrap_width` is zero or negative, the
+/// available width of the window is used. This gives full control over where a legend goes, for
+/// example below a row of plots that use `PlotFlags::NO_LEGEND`, with items taken from
+/// [`PlotToken::items`]. Hidden `##` parts of labels are not shown. Call this outside of plots;
+/// unlike ImPlot's legend, clicking the entries does not hide items.
+pub fn draw_legend(items: &[(String, ImVec4)], wrap_width: f32) {
+    let style = unsafe { &*sys::igGetStyle() };
+    let line_height = unsafe { sys::igGetTextLineHeight() };
+    let wrap_width = if wrap_width > 0.0 {
+        wrap_width
+    } else {
+        let mut available = ImVec2 { x: 0.0, y: 0.0 };
+        unsafe { sys::igGetContentRegionAvail(&mut available as *mut ImVec2) };
+        available.x
+    };
+    let mut origin = ImVec2 { x: 0.0, y: 0.0 };
+    unsafe { sys::igGetCursorScreenPos(&mut origin as *mut ImVec2) };
+    let text_color = unsafe { sys::igGetColorU32_Col(sys::ImGuiCol_Text as i32, 1.0) };
+
+    // Position of the next entry relative to the origin, and the width used by the widest row
+    let (mut x, mut y, mut used_width) = (0.0f32, 0.0f32, 0.0f32);
+    for (label, color) in items {
+        let label = label.split("##").next().unwrap_or("");
+        let text_begin = label.as_ptr() as *const c_char;
+        let mut text_size = ImVec2 { x: 0.0, y: 0.0 };
+        unsafe {
+            let text_end = text_begin.add(label.len());
+            sys::igCalcTextSize(
+                &mut text_size as *mut ImVec2,
+                text_begin,
+                text_end,
+                false,
+                -1.0,
+            );
+        }
+        let entry_width = line_height + style.ItemInnerSpacing.x + text_size.x;
+        if x > 0.0 && x + entry_width > wrap_width {
+            x = 0.0;
+            y += line_height + style.ItemSpacing.y;
+        }
+
+        let position = ImVec2 {
+            x: origin.x + x,
+            y: origin.y + y,
+        };
+        unsafe {
+            let draw_list = sys::igGetWindowDrawList();
+            sys::ImDrawList_AddRectFilled(
+                draw_list,
+                position,
+                ImVec2 {
+                    x: position.x + line_height,
+                    y: position.y + line_height,
+                },
+                sys::igColorConvertFloat4ToU32(*color),
+                0.0,
+                0,
+            );
+            sys::ImDrawList_AddText_Vec2(
+                draw_list,
+                ImVec2 {
+                    x: position.x + line_height + style.ItemInnerSpacing.x,
+                    y: position.y,
+                },
+                text_color,
+                text_begin,
+                text_begin.add(label.len()),
+            );
+        }
+        used_width = used_width.max(x + entry_width);
+        x += entry_width + style.ItemSpacing.x;
+    }
+
+    // Reserve the space the entries took, so the widget is laid out like any other
+    let height = if items.is_empty() {
+        0.0
+    } else {
+        y + line_height
+    };
+    unsafe {
+        sys::igDummy(ImVec2 {
+            x: used_width,
+            y: height,
+        })
+    };
+}
+
 /// Returns true if the given item in the legend of the current plot is hovered.
 pub fn is_legend_entry_hovered(legend_entry: &str) -> bool {
     unsafe { sys::ImPlot_IsLegendEntryHovered(legend_entry.as_ptr() as *const c_char) }
 }
 
+// --- Drag tools --------------------------------------------------------------------------------
+/// Show a point the user can drag around, at the position given by `x` and `y` in plot
+/// coordinates, which are updated while the point is dragged. Returns whether the position was
+/// changed. `id` has to be unique among the drag tools of the current plot, and `size` is the
+/// radius of the point in pixels. Only call this between beginning and ending a plot.
+#[rustversion::attr(since(1.48), doc(alias = "DragPoint"))]
+pub fn drag_point(
+    id: i32,
+    x: &mut f64,
+    y: &mut f64,
+    color: ImVec4,
+    size: f32,
+    flags: DragToolFlags,
+) -> bool {
+    unsafe {
+        sys::ImPlot_DragPoint(
+            id,
+            x as *mut f64,
+            y as *mut f64,
+            color,
+            size,
+            flags.bits() as sys::ImPlotDragToolFlags,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    }
+}
+
+/// Show a horizontal line the user can drag up and down, at the Y value given by `y` in plot
+/// coordinates, which is updated while the line is dragged. Returns whether the value was
+/// changed. `id` has to be unique among the drag tools of the current plot, and `thickness` is
+/// the width of the line in pixels. Only call this between beginning and ending a plot.
+#[rustversion::attr(since(1.48), doc(alias = "DragLineY"))]
+pub fn drag_line_y(
+    id: i32,
+    y: &mut f64,
+    color: ImVec4,
+    thickness: f32,
+    flags: DragToolFlags,
+) -> bool {
+    unsafe {
+        sys::ImPlot_DragLineY(
+            id,
+            y as *mut f64,
+            color,
+            thickness,
+            flags.bits() as sys::ImPlotDragToolFlags,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    }
+}
+
 // --- Demo window -------------------------------------------------------------------------------
 /// Show the demo window for poking around what functionality implot has to
 /// offer. Note that not all of this is necessarily implemented in implot-rs
@@ -629,3 +1397,51 @@ pub fn show_demo_window(show: &mut bool) {
         implot_sys::ImPlot_ShowDemoWindow(show);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nice_tick_positions() {
+        assert_eq!(nice_ticks(0.0, 10.0, 800.0, false), vec![0.0, 10.0]);
+        assert_eq!(nice_ticks(0.0, 10.0, 1600.0, false), vec![0.0, 5.0, 10.0]);
+        assert_eq!(nice_ticks(0.0, 10.0, 1200.0, true), vec![0.0, 5.0, 10.0]);
+        assert!(nice_ticks(1.0, 1.0, 800.0, false).is_empty());
+
+        let ticks = nice_ticks(-0.3, 0.3, 2800.0, false);
+        assert_eq!(ticks.len(), 3);
+        assert!((ticks[0] + 0.2).abs() < 1e-12);
+        assert_eq!(ticks[1], 0.0);
+        assert!((ticks[2] - 0.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn stale_token_after_clear() {
+        reset_stack_counts();
+        let stale = note_pushed(Stack::StyleColors);
+        // The bookkeeping of force_clear_style_stacks(), without the ImPlot calls
+        assert_eq!(clear_style_counts().style_colors, 1);
+
+        let fresh = note_pushed(Stack::StyleColors);
+        assert!(!take_style_pushed(Stack::StyleColors, stale));
+        assert_eq!(STACK_COUNTS.with(|counts| counts.borrow().style_colors), 1);
+        assert!(take_style_pushed(Stack::StyleColors, fresh));
+        assert_eq!(STACK_COUNTS.with(|counts| counts.borrow().style_colors), 0);
+    }
+
+    #[test]
+    fn stale_token_after_clear_and_reset() {
+        reset_stack_counts();
+        let stale = note_pushed(Stack::StyleColors);
+        clear_style_counts();
+        // A new context must not make tokens from before the clear valid again
+        reset_stack_counts();
+
+        let fresh = note_pushed(Stack::StyleColors);
+        assert!(!take_style_pushed(Stack::StyleColors, stale));
+        assert_eq!(STACK_COUNTS.with(|counts| counts.borrow().style_colors), 1);
+        assert!(take_style_pushed(Stack::StyleColors, fresh));
+        assert_eq!(STACK_COUNTS.with(|counts| counts.borrow().style_colors), 0);
+    }
+}
@@ -2,35 +2,43 @@
 //!
 //! This module defines the `Plot` struct, which is used to create a 2D plot that will
 //! contain all other objects that can be created using this library.
-use crate::{get_x_axis_from_index, get_x_axis_index, get_y_axis_from_index, get_y_axis_index, Axis, Context, PlotLocation, PlotUi, NUMBER_OF_X_AXES, NUMBER_OF_Y_AXES};
+use crate::callback::{
+    formatter_trampoline, forward_transform_trampoline, inverse_transform_trampoline, CallbackData,
+};
+use crate::context::{DEFAULT_PLOT_SIZE_X, DEFAULT_PLOT_SIZE_Y};
+use crate::custom_elements::{draw_corner_text, draw_rotated_x_tick_labels};
+use crate::quantity::{quantity_label, Quantity};
+use crate::{get_x_axis_from_index, get_x_axis_index, get_y_axis_from_index, get_y_axis_index, get_plot_limits, get_plot_size, push_style_color, push_style_var_f32, push_style_var_imvec2, Axis, AxisScale, Context, DataExtents, ItemInfo, PlotColorElement, PlotLocation, PlotUi, StyleColorToken, StyleVar, StyleVarToken, XAxisSide, YAxisSide, NUMBER_OF_X_AXES, NUMBER_OF_Y_AXES};
 use bitflags::bitflags;
 pub use imgui::Condition;
 use implot_sys as sys;
-use std::ffi::CString;
-use std::os::raw::c_char;
+use std::borrow::Cow;
+use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
+use std::os::raw::{c_char, c_int, c_void};
 use std::{
     cell::RefCell, 
-    rc::Rc
+    rc::Rc,
+    time::Instant,
 };
 pub use sys::{
     ImPlotRange,
     ImVec2,
 };
-
-const DEFAULT_PLOT_SIZE_X: f32 = 400.0;
-const DEFAULT_PLOT_SIZE_Y: f32 = 400.0;
+use sys::ImVec4;
 
 bitflags! {
     /// Flags for customizing plot behavior and interaction. Documentation copied from implot.h for
-    /// convenience. ImPlot itself also has a "CanvasOnly" flag, which can be emulated here with
-    /// the combination of `NO_LEGEND`, `NO_MENUS`, `NO_BOX_SELECT` and `NO_MOUSE_POSITION`.
+    /// convenience. This covers all plot flags of the vendored ImPlot version. Older ImPlot
+    /// versions also had flags for the child window plots used to be drawn in (such as
+    /// `NoChild`), but plots are no longer drawn in a child window, so those do not exist anymore.
     #[repr(transparent)]
     pub struct PlotFlags: u32 {
         /// "Default" according to original docs
         const NONE = sys::ImPlotFlags__ImPlotFlags_None as u32;
         /// the plot title will not be displayed (titles are also hidden if preceeded by double hashes, e.g. "##MyPlot")
         const NO_TITLE = sys::ImPlotFlags__ImPlotFlags_NoTitle as u32;
-        /// Plot items will not be highlighted when their legend entry is hovered
+        /// the legend will not be displayed
         const NO_LEGEND = sys::ImPlotFlags__ImPlotFlags_NoLegend as u32;
         /// the mouse position, in plot coordinates, will not be displayed inside of the plot
         const NO_MOUSE_TEXT = sys::ImPlotFlags__ImPlotFlags_NoMouseText as u32;
@@ -40,13 +48,17 @@ bitflags! {
         const NO_MENUS = sys::ImPlotFlags__ImPlotFlags_NoMenus as u32;
         /// The user will not be able to box-select with right-mouse
         const NO_BOX_SELECT = sys::ImPlotFlags__ImPlotFlags_NoBoxSelect as u32;
-        /// The mouse position, in plot coordinates, will not be displayed
         /// the ImGui frame will not be rendered
         const NO_FRAME = sys::ImPlotFlags__ImPlotFlags_NoFrame as u32;
-        /// x and y axes pairs will be constrained to have the same units/pixel
+        /// x and y axes pairs will be constrained to have the same units/pixel. ImPlot only applies
+        /// this to the X1/Y1 pair, so any additional axes (for example a data axis on Y2 next to
+        /// a geographic overlay on X1/Y1) keep scaling freely.
         const EQUAL = sys::ImPlotFlags__ImPlotFlags_Equal as u32;
         /// the default mouse cursor will be replaced with a crosshair when hovered
-        const CROSSHAIRS = sys::ImPlotFlags__ImPlotFlags_Equal as u32;
+        const CROSSHAIRS = sys::ImPlotFlags__ImPlotFlags_Crosshairs as u32;
+        /// Only the plot canvas is shown, the combination of `NO_TITLE`, `NO_LEGEND`, `NO_MENUS`,
+        /// `NO_BOX_SELECT` and `NO_MOUSE_TEXT`
+        const CANVAS_ONLY = sys::ImPlotFlags__ImPlotFlags_CanvasOnly as u32;
     }
 }
 
@@ -78,7 +90,9 @@ bitflags! {
         const FOREGROUND = sys::ImPlotAxisFlags__ImPlotAxisFlags_Foreground as u32;
         /// The axis will be inverted
         const INVERT = sys::ImPlotAxisFlags__ImPlotAxisFlags_Invert as u32;
+        /// axis will be auto-fitting to data extents
         const AUTO_FIT = sys::ImPlotAxisFlags__ImPlotAxisFlags_AutoFit as u32;
+        /// axis will only fit points if the point is in the visible range of the **orthogonal** axis
         const RANGE_FIT = sys::ImPlotAxisFlags__ImPlotAxisFlags_RangeFit as u32;
         const PAN_STRETCH = sys::ImPlotAxisFlags__ImPlotAxisFlags_PanStretch as u32;
         /// The axis minimum value will be locked when panning/zooming
@@ -120,6 +134,13 @@ bitflags! {
     }
 }
 
+bitflags! {
+    #[repr(transparent)]
+    pub struct ShadedFlags: u32 {
+        const NONE = sys::ImPlotShadedFlags__ImPlotShadedFlags_None   as u32;       // default
+    }
+}
+
 bitflags! {
     #[repr(transparent)]
     pub struct BarsFlags: u32 {
@@ -128,6 +149,55 @@ bitflags! {
     }
 }
 
+bitflags! {
+    #[repr(transparent)]
+    pub struct ErrorBarsFlags: u32 {
+        const NONE = sys::ImPlotErrorBarsFlags__ImPlotErrorBarsFlags_None   as u32;       // default
+        const HORIZONTAL = sys::ImPlotErrorBarsFlags__ImPlotErrorBarsFlags_Horizontal as u32; // error bars will be rendered horizontally on the current y-axis
+    }
+}
+
+bitflags! {
+    #[repr(transparent)]
+    pub struct DigitalFlags: u32 {
+        const NONE = sys::ImPlotDigitalFlags__ImPlotDigitalFlags_None   as u32;       // default
+    }
+}
+
+bitflags! {
+    #[repr(transparent)]
+    pub struct ImageFlags: u32 {
+        const NONE = sys::ImPlotImageFlags__ImPlotImageFlags_None   as u32;       // default
+    }
+}
+
+bitflags! {
+    #[repr(transparent)]
+    pub struct InfLinesFlags: u32 {
+        const NONE = sys::ImPlotInfLinesFlags__ImPlotInfLinesFlags_None   as u32;       // default
+        const HORIZONTAL = sys::ImPlotInfLinesFlags__ImPlotInfLinesFlags_Horizontal as u32; // lines will be rendered horizontally on the current y-axis
+    }
+}
+
+bitflags! {
+    #[repr(transparent)]
+    pub struct HistogramFlags: u32 {
+        const NONE = sys::ImPlotHistogramFlags__ImPlotHistogramFlags_None   as u32;       // default
+        const HORIZONTAL = sys::ImPlotHistogramFlags__ImPlotHistogramFlags_Horizontal as u32; // histogram bars will be rendered horizontally
+        const CUMULATIVE = sys::ImPlotHistogramFlags__ImPlotHistogramFlags_Cumulative as u32; // each bin will contain its count plus the counts of all previous bins
+        const DENSITY = sys::ImPlotHistogramFlags__ImPlotHistogramFlags_Density as u32; // counts will be normalized, i.e. the PDF will be visualized, or the CDF will be visualized if Cumulative is also set
+        const NO_OUTLIERS = sys::ImPlotHistogramFlags__ImPlotHistogramFlags_NoOutliers as u32; // exclude values outside the specifed histogram range from the count toward normalizing and cumulative counts
+        const COL_MAJOR = sys::ImPlotHistogramFlags__ImPlotHistogramFlags_ColMajor as u32; // data will be read in column major order (not supported by PlotHistogram)
+    }
+}
+
+bitflags! {
+    #[repr(transparent)]
+    pub struct DummyFlags: u32 {
+        const NONE = sys::ImPlotDummyFlags__ImPlotDummyFlags_None   as u32;       // default
+    }
+}
+
 bitflags! {
     #[repr(transparent)]
     pub struct TextFlags: u32 {
@@ -153,6 +223,38 @@ bitflags! {
 }
 
 bitflags! {
+    #[repr(transparent)]
+    pub struct DragToolFlags: u32 {
+        const NONE = sys::ImPlotDragToolFlags__ImPlotDragToolFlags_None      as u32; // default
+        const NO_CURSORS = sys::ImPlotDragToolFlags__ImPlotDragToolFlags_NoCursors as u32; // drag tools won't change cursor icons when hovered or held
+        const NO_FIT = sys::ImPlotDragToolFlags__ImPlotDragToolFlags_NoFit     as u32; // the drag tool won't be considered for plot fits
+        const NO_INPUTS = sys::ImPlotDragToolFlags__ImPlotDragToolFlags_NoInputs  as u32; // lock the tool from user inputs
+        const DELAYED = sys::ImPlotDragToolFlags__ImPlotDragToolFlags_Delayed   as u32; // tool rendering will be delayed one frame; useful when applying position-constraints
+    }
+}
+
+bitflags! {
+    /// Statistics of a series to draw along with it, see `PlotLine::plot_with_stat_lines`. These
+    /// are not ImPlot flags, the statistics are computed and drawn by this crate.
+    #[repr(transparent)]
+    pub struct StatLines: u32 {
+        /// A horizontal line at the mean of the values
+        const MEAN = 1 << 0;
+        /// A horizontal line at the median of the values
+        const MEDIAN = 1 << 1;
+        /// A shaded band from one standard deviation below the mean to one above it
+        const STD_BANDS = 1 << 2;
+    }
+}
+
+bitflags! {
+    /// Legend flags. Note that these apply to the legend as a whole - ImPlot has no way of
+    /// setting them for individual entries. In particular, `NO_HIGHLIGHT_ITEM` turns off
+    /// highlighting for every item in the plot. To keep some "background" items from being
+    /// highlighted while the others still are, plot those items with a label starting with `##`
+    /// (which excludes them from the legend, and with that from highlighting) and, if they should
+    /// still be listed, add a manual legend entry for them that draws nothing, as shown in the
+    /// `line_plots` example.
     #[repr(transparent)]
     pub struct LegendFlags: u32 {
         const NONE = sys::ImPlotLegendFlags__ImPlotLegendFlags_None            as u32;      // default
@@ -173,6 +275,398 @@ enum AxisLimitSpecification {
     Single(ImPlotRange, Condition),
     /// Limits that are linked to limits of other plots (via clones of the same Rc)
     Linked(Rc<RefCell<ImPlotRange>>),
+    /// Limits that are read from and written back to a range borrowed by the plot
+    Stateful(*mut ImPlotRange),
+}
+
+/// Function mapping a value to another, used for custom axis scales, see
+/// [`Plot::with_axis_transform`].
+pub type AxisTransform = fn(f64) -> f64;
+
+/// Internally-used callback data for a custom axis scale, holding the forward and inverse transform
+type AxisTransformData = CallbackData<(AxisTransform, AxisTransform)>;
+
+/// Custom ticks for an axis, kept by the caller across frames and borrowed by plots with
+/// [`Plot::x_ticks_cached`] or [`Plot::y_ticks_cached`]. Unlike [`Plot::x_ticks_with_labels`],
+/// which converts the labels on every frame, this converts them once, so setting up the ticks
+/// does not allocate at all. Create it again when the ticks change. The "Many custom ticks" line
+/// plot demo shows the allocations and time of a frame with 500 ticks set up either way. It is
+/// an interactive demo rather than a benchmark, so the timings are only indicative.
+pub struct CachedTicks {
+    /// Positions of the ticks
+    positions: Vec<f64>,
+    /// Labels of the ticks, which the pointers point into. Empty if the ticks have no labels.
+    labels: Vec<CString>,
+    /// Pointers to the labels, as ImPlot takes them. Empty if the ticks have no labels.
+    label_pointers: Vec<*const c_char>,
+    /// Whether the default ticks are also shown
+    show_default: bool,
+}
+
+impl CachedTicks {
+    /// Create ticks with labels, given as tuples `(label_position, label_string)`. The
+    /// `show_default` setting determines whether the default ticks are also shown.
+    ///
+    /// # Panics
+    /// Will panic if any of the tick label strings contain internal null bytes.
+    pub fn new(tick_labels: &[(f64, String)], show_default: bool) -> Self {
+        let labels: Vec<CString> = tick_labels
+            .iter()
+            .map(|x| {
+                CString::new(x.1.as_str())
+                    .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", x.1))
+            })
+            .collect();
+        // The heap buffers of the labels do not move when the vector holding them is moved, so
+        // these stay valid for as long as the labels are alive
+        let label_pointers = labels.iter().map(|x| x.as_ptr()).collect();
+        Self {
+            positions: tick_labels.iter().map(|x| x.0).collect(),
+            labels,
+            label_pointers,
+            show_default,
+        }
+    }
+
+    /// Create ticks without labels at the given positions. The `show_default` setting
+    /// determines whether the default ticks are also shown.
+    pub fn without_labels(ticks: &[f64], show_default: bool) -> Self {
+        Self {
+            positions: ticks.into(),
+            labels: Vec::new(),
+            label_pointers: Vec::new(),
+            show_default,
+        }
+    }
+
+    /// Internal helper to set up these ticks on the given axis.
+    fn setup(&self, axis: Axis) {
+        if self.positions.is_empty() {
+            return;
+        }
+        let labels_pointer = if self.labels.is_empty() {
+            std::ptr::null_mut()
+        } else {
+            // ImPlot only reads the labels, despite taking a mutable pointer
+            self.label_pointers.as_ptr() as *mut *const c_char
+        };
+        unsafe {
+            sys::ImPlot_SetupAxisTicks_doublePtr(
+                axis as i32,
+                self.positions.as_ptr(),
+                self.positions.len() as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                labels_pointer,
+                self.show_default,
+            )
+        }
+    }
+}
+
+/// The complete zoom and pan state of a plot, as the limits and scales of all its axes, for
+/// saving and restoring it. Capture it with [`PlotToken::capture_viewport`] and restore it with
+/// [`Plot::with_viewport`]. With the `serde` feature enabled, it can be serialized.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Viewport {
+    /// Limits of the X axes X1 to X3 as `[min, max]`, or `None` for axes the plot does not use
+    pub x_limits: [Option<[f64; 2]>; NUMBER_OF_X_AXES],
+    /// Limits of the Y axes Y1 to Y3 as `[min, max]`, or `None` for axes the plot does not use
+    pub y_limits: [Option<[f64; 2]>; NUMBER_OF_Y_AXES],
+    /// Scales of the X axes X1 to X3
+    pub x_scales: [AxisScale; NUMBER_OF_X_AXES],
+    /// Scales of the Y axes Y1 to Y3
+    pub y_scales: [AxisScale; NUMBER_OF_Y_AXES],
+}
+
+/// State of an animated transition of the limits of one axis towards target limits, see
+/// [`Plot::animate_limits`]. Keep it around across frames while the animation runs, and create a
+/// new one to start another animation, for example when a "jump to region" button is clicked.
+#[derive(Copy, Clone, Debug)]
+pub struct LimitsAnimation {
+    /// Axis whose limits are animated
+    axis: Axis,
+    /// Limits at the end of the animation
+    target: ImPlotRange,
+    /// Duration of the animation, in seconds
+    duration: f64,
+    /// Limits the animation started from and when it started, once it has started
+    start: Option<(ImPlotRange, Instant)>,
+    /// Whether the target limits were reached
+    finished: bool,
+}
+
+impl LimitsAnimation {
+    /// Create an animation of the limits of `axis` towards `target`, taking `duration_s`
+    /// seconds. It starts on the first frame it is applied to a plot with
+    /// [`Plot::animate_limits`].
+    pub fn new(axis: Axis, target: ImPlotRange, duration_s: f64) -> Self {
+        Self {
+            axis,
+            target,
+            duration: duration_s,
+            start: None,
+            finished: false,
+        }
+    }
+
+    /// Whether the animation has reached its target limits.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Internal helper to get the limits for the current point in time, given the time the
+    /// animation started at and the limits it started from. Eases in and out, so the view
+    /// neither starts nor stops moving abruptly.
+    fn current_limits(&mut self, from: ImPlotRange, started: Instant) -> ImPlotRange {
+        let t = if self.duration > 0.0 {
+            (started.elapsed().as_secs_f64() / self.duration).min(1.0)
+        } else {
+            1.0
+        };
+        self.finished = t >= 1.0;
+        // Cubic ease-in-out
+        let eased = if t < 0.5 {
+            4.0 * t * t * t
+        } else {
+            1.0 - (2.0 - 2.0 * t).powi(3) / 2.0
+        };
+        ImPlotRange {
+            Min: from.Min + (self.target.Min - from.Min) * eased,
+            Max: from.Max + (self.target.Max - from.Max) * eased,
+        }
+    }
+}
+
+/// Internally-used closure turning tick values into tick labels
+type AxisFormatter = Box<dyn FnMut(f64) -> String>;
+
+/// Internally-used callback data for a custom tick label format
+type AxisFormatterData = CallbackData<AxisFormatter>;
+
+/// Internally-used rotated tick labels of an X axis, as axis, angle and `(position, label)` pairs
+type RotatedTickLabels = (Axis, f32, Vec<(f64, String)>);
+
+/// SI prefixes from 10^-24 to 10^24, in steps of a factor 1000
+const SI_PREFIXES: [&str; 17] = [
+    "y", "z", "a", "f", "p", "n", "\u{b5}", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y",
+];
+
+/// Internal helper to format a value in engineering notation with an SI prefix, followed by the
+/// given unit, for example 1500 with unit "Hz" as "1.5kHz". Up to three decimals are kept.
+fn format_si_prefixed(value: f64, unit: &str) -> String {
+    if value == 0.0 {
+        // Covers -0 as well, which should not be labeled with a sign
+        return format!("0{}", unit);
+    }
+    if !value.is_finite() {
+        return format!("{}{}", value, unit);
+    }
+
+    let mut exponent = ((value.abs().log10() / 3.0).floor() as i32).clamp(-8, 8);
+    let mut mantissa = value / 1000f64.powi(exponent);
+    // Rounding may carry over into the next prefix, e.g. 999.9999 should become "1k"
+    mantissa = (mantissa * 1000.0).round() / 1000.0;
+    if mantissa.abs() >= 1000.0 && exponent < 8 {
+        exponent += 1;
+        mantissa /= 1000.0;
+    }
+
+    let mut number = format!("{:.3}", mantissa);
+    if number.contains('.') {
+        number = number.trim_end_matches('0').trim_end_matches('.').to_string();
+    }
+    format!("{}{}{}", number, SI_PREFIXES[(exponent + 8) as usize], unit)
+}
+
+/// Format a value for display, in fixed notation if its magnitude is between 0.001 and 100000
+/// and in scientific notation otherwise, with four significant digits and without trailing
+/// zeros, for example "0.125", "42", "1.5e6" or "-2.5e-7". The text is written into `buf`,
+/// truncated to leave room for a terminating null byte, which is written as well. Returns the
+/// number of bytes written, not counting the null byte, which is 0 for an empty buffer.
+///
+/// This is the format used by [`Plot::with_auto_format_axis`] and
+/// [`PlotHeatmap::with_auto_labels`](crate::PlotHeatmap::with_auto_labels), and can be used in
+/// custom formatters to get consistent numbers everywhere.
+pub fn auto_format(value: f64, buf: &mut [u8]) -> usize {
+    let text = if value == 0.0 {
+        // Covers -0 as well, which should not be labeled with a sign
+        "0".to_string()
+    } else if !value.is_finite() {
+        value.to_string()
+    } else if (1e-3..1e5).contains(&value.abs()) {
+        let decimals = (3 - value.abs().log10().floor() as i32).max(0) as usize;
+        let text = format!("{:.*}", decimals, value);
+        if text.contains('.') {
+            text.trim_end_matches('0').trim_end_matches('.').to_string()
+        } else {
+            text
+        }
+    } else {
+        let text = format!("{:.3e}", value);
+        let (mantissa, exponent) = text.split_at(text.find('e').unwrap_or(text.len()));
+        let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+        format!("{}{}", mantissa, exponent)
+    };
+
+    if buf.is_empty() {
+        return 0;
+    }
+    let length = text.len().min(buf.len() - 1);
+    buf[..length].copy_from_slice(&text.as_bytes()[..length]);
+    buf[length] = 0;
+    length
+}
+
+/// Internal helper to shorten a label so that it is at most `max_width` wide as measured by
+/// `text_width`, by cutting off characters at the end and appending "...". Returns `None` if the
+/// label fits already. If not even the ellipsis fits, the ellipsis alone is returned.
+fn truncate_label(label: &str, max_width: f32, text_width: impl Fn(&str) -> f32) -> Option<String> {
+    if text_width(label) <= max_width {
+        return None;
+    }
+    let mut end = label.len();
+    while let Some((index, _)) = label[..end].char_indices().next_back() {
+        end = index;
+        let candidate = format!("{}...", label[..end].trim_end());
+        if text_width(&candidate) <= max_width {
+            return Some(candidate);
+        }
+    }
+    Some("...".to_string())
+}
+
+/// Internal helper to measure the width of a text in pixels with the current ImGui font.
+fn imgui_text_width(text: &str) -> f32 {
+    let mut size = ImVec2 { x: 0.0, y: 0.0 };
+    unsafe {
+        let text_begin = text.as_ptr() as *const c_char;
+        let text_end = text_begin.add(text.len());
+        sys::igCalcTextSize(&mut size as *mut ImVec2, text_begin, text_end, false, -1.0);
+    }
+    size.x
+}
+
+/// Internal formatter matching `ImPlotFormatter` that labels ticks with [`auto_format`]. It
+/// needs no closure, so unlike other formatters it is passed to ImPlot without callback data.
+unsafe extern "C" fn auto_formatter(
+    value: f64,
+    buff: *mut c_char,
+    size: c_int,
+    _user_data: *mut c_void,
+) -> c_int {
+    if buff.is_null() || size <= 0 {
+        return 0;
+    }
+    let buf = std::slice::from_raw_parts_mut(buff as *mut u8, size as usize);
+    auto_format(value, buf) as c_int
+}
+
+/// Internally-used value of a style variable that only applies to one plot
+#[derive(Copy, Clone)]
+enum StyleVarValue {
+    F32(f32),
+    Vec2(ImVec2),
+}
+
+impl From<f32> for StyleVarValue {
+    fn from(value: f32) -> Self {
+        StyleVarValue::F32(value)
+    }
+}
+
+impl From<[f32; 2]> for StyleVarValue {
+    fn from(value: [f32; 2]) -> Self {
+        StyleVarValue::Vec2(ImVec2 { x: value[0], y: value[1] })
+    }
+}
+
+/// Internal helper to turn the condition of direct axis limits into the condition to pass to
+/// ImPlot, or `None` if the limits should not be set on this frame. ImPlot itself only knows
+/// "always" and "once" (which covers `FirstUseEver` as well), so `Appearing` is implemented
+/// here, by setting the limits unconditionally on frames where the plot appears, and `Never`
+/// does not set the limits at all.
+fn limit_condition(condition: Condition, appearing: bool) -> Option<sys::ImGuiCond> {
+    match condition {
+        Condition::Never => None,
+        Condition::Appearing if appearing => Some(Condition::Always as sys::ImGuiCond),
+        Condition::Appearing => None,
+        _ => Some(condition as sys::ImGuiCond),
+    }
+}
+
+/// Internal helper to get the size new plots get by default, which is stored in the style of the
+/// current context. Falls back to the initial default if there is no current context.
+fn default_plot_size() -> [f32; 2] {
+    unsafe {
+        if sys::ImPlot_GetCurrentContext().is_null() {
+            return [DEFAULT_PLOT_SIZE_X, DEFAULT_PLOT_SIZE_Y];
+        }
+        let style = sys::ImPlot_GetStyle();
+        [(*style).PlotDefaultSize.x, (*style).PlotDefaultSize.y]
+    }
+}
+
+/// Internal helper to draw an infinite line at zero on the first axes, which is neither shown in
+/// the legend nor taken into account when fitting the plot to its data.
+fn draw_zero_line(label: &str, color: ImVec4, direction_flags: sys::ImPlotInfLinesFlags) {
+    let label = CString::new(label).unwrap();
+    let flags = sys::ImPlotItemFlags__ImPlotItemFlags_NoLegend
+        | sys::ImPlotItemFlags__ImPlotItemFlags_NoFit
+        | direction_flags;
+    let zero = 0.0f64;
+    unsafe {
+        crate::set_first_axes();
+        sys::ImPlot_SetNextLineStyle(color, 1.0);
+        sys::ImPlot_PlotInfLines_doublePtr(
+            label.as_ptr(),
+            &zero,
+            1,
+            flags,
+            0,                                 // No offset
+            std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+        );
+    }
+}
+
+/// Internal helper to check whether an imgui frame is active, i.e. whether the imgui context has
+/// started a frame that was not rendered yet. Imgui has no public query for this, but its frame
+/// count is zero until the first frame is started, and its draw data is only available from
+/// rendering a frame until the next frame is started.
+fn imgui_frame_active() -> bool {
+    unsafe {
+        !sys::igGetCurrentContext().is_null()
+            && sys::igGetFrameCount() > 0
+            && sys::igGetDrawData().is_null()
+    }
+}
+
+/// Internal helper to dim the plot frame between the given corners like imgui dims disabled
+/// widgets, by drawing the window background color over it.
+fn draw_disabled_overlay(frame_min: ImVec2, frame_max: ImVec2) {
+    unsafe {
+        let style = &*sys::igGetStyle();
+        let mut color = style.Colors[sys::ImGuiCol_WindowBg as usize];
+        color.w = 1.0 - style.DisabledAlpha;
+        sys::ImDrawList_AddRectFilled(
+            sys::igGetWindowDrawList(),
+            frame_min,
+            frame_max,
+            sys::igColorConvertFloat4ToU32(color),
+            style.FrameRounding,
+            0,
+        );
+    }
+}
+
+/// Internal helper to add the "+K more" entry at the end of a legend that was limited with
+/// [`Plot::with_max_legend_entries`], as an item without data. Its ID stays the same when the
+/// count changes, so ImPlot keeps treating it as the same item.
+fn draw_legend_overflow_entry(hidden_entries: usize) {
+    let label = CString::new(format!("+{} more###legend overflow", hidden_entries)).unwrap();
+    unsafe {
+        sys::ImPlot_SetNextLineStyle(ImVec4 { x: 0.5, y: 0.5, z: 0.5, w: 1.0 }, 1.0);
+        sys::ImPlot_PlotDummy(label.as_ptr(), 0);
+    }
 }
 
 /// Struct to represent an ImPlot. This is the main construct used to contain all kinds of plots in ImPlot.
@@ -191,18 +685,26 @@ enum AxisLimitSpecification {
 /// ```
 /// (If you are coming from the C++ implementation or the C bindings: build() calls both
 /// begin() and end() internally)
-pub struct Plot {
+pub struct Plot<'a> {
     /// Title of the plot, shown on top. Stored as CString because that's what we'll use
     /// afterwards, and this ensures the CString itself will stay alive long enough for the plot.
     title: CString,
     /// Size of the plot in [x, y] direction, in the same units imgui uses.
     size: [f32; 2],
-    /// Label of the x axis, shown on the bottom. Stored as CString because that's what we'll use
-    /// afterwards, and this ensures the CString itself will stay alive long enough for the plot.
-    x_label: CString,
-    /// Label of the y axis, shown on the left. Stored as CString because that's what we'll use
-    /// afterwards, and this ensures the CString itself will stay alive long enough for the plot.
-    y_label: CString,
+    /// Labels of the x axes, the first one is shown on the bottom. Stored as CString because
+    /// that's what we'll use afterwards, and this ensures the CString itself will stay alive long
+    /// enough for the plot.
+    x_labels: [CString; NUMBER_OF_X_AXES],
+    /// Labels of the y axes, the first one is shown on the left. Stored as CString because
+    /// that's what we'll use afterwards, and this ensures the CString itself will stay alive long
+    /// enough for the plot.
+    y_labels: [CString; NUMBER_OF_Y_AXES],
+    /// Whether the X axes are enabled. The first X axis always is, the others are enabled as soon
+    /// as anything (label, limits, ticks or flags) is configured for them.
+    x_enabled: [bool; NUMBER_OF_X_AXES],
+    /// Whether the Y axes are enabled. The first Y axis always is, the others are enabled as soon
+    /// as anything (label, limits, ticks or flags) is configured for them.
+    y_enabled: [bool; NUMBER_OF_Y_AXES],
     /// X axis limits, if present
     x_limits: [Option<AxisLimitSpecification>; NUMBER_OF_X_AXES],
     /// Y axis limits, if present
@@ -218,6 +720,17 @@ pub struct Plot {
     x_tick_labels: [Option<Vec<CString>>; NUMBER_OF_X_AXES],
     /// Whether to also show the default X ticks when showing custom ticks or not
     show_x_default_ticks: [bool; NUMBER_OF_X_AXES],
+    /// Angle in degrees to rotate the custom X axis tick labels by, if any. ImPlot cannot rotate
+    /// tick labels, so if this is set, ImPlot's labels are hidden and the rotated ones are drawn
+    /// by this crate instead.
+    x_tick_label_angles: [Option<f32>; NUMBER_OF_X_AXES],
+    /// Maximum width in pixels of custom tick labels, longer ones are shortened with an ellipsis
+    tick_label_max_width: Option<f32>,
+    /// Scale and offset mapping the limits of the first X axis to those of each X axis, for axes
+    /// that show the same range as the first one in different units
+    x_mirrors: [Option<(f64, f64)>; NUMBER_OF_X_AXES],
+    /// Ratio of the pixels per unit of the first Y axis to those of the first X axis to keep
+    aspect_ratio: Option<f64>,
     /// Positions for custom Y axis ticks, if any
     y_tick_positions: [Option<Vec<f64>>; NUMBER_OF_Y_AXES],
     /// Labels for custom Y axis ticks, if any. I'd prefer to store these together
@@ -227,6 +740,10 @@ pub struct Plot {
     /// convert to null-terminated data anyway, we may as well do that directly instead of cloning
     /// Strings and converting them afterwards.
     y_tick_labels: [Option<Vec<CString>>; NUMBER_OF_Y_AXES],
+    /// Ticks for the X axes that the caller keeps across frames, see [`CachedTicks`]
+    x_cached_ticks: [Option<&'a CachedTicks>; NUMBER_OF_X_AXES],
+    /// Ticks for the Y axes that the caller keeps across frames, see [`CachedTicks`]
+    y_cached_ticks: [Option<&'a CachedTicks>; NUMBER_OF_Y_AXES],
     /// Whether to also show the default Y ticks when showing custom ticks or not
     show_y_default_ticks: [bool; NUMBER_OF_Y_AXES],
     /// Configuration for the legend, if specified. The tuple contains location, orientation
@@ -235,15 +752,63 @@ pub struct Plot {
     /// interactive legend configuration does not work because it is overridden by the settings
     /// here.
     legend_configuration: Option<(PlotLocation, LegendFlags)>,
+    /// Maximum number of items listed in the legend, if limited
+    max_legend_entries: Option<usize>,
     /// Flags relating to the plot TODO(4bb4) make those into bitflags
     plot_flags: PlotFlags,
+    /// Text to show in the center of the plot if nothing was plotted into it
+    empty_text: Option<CString>,
+    /// Whether to show the time spent in the closure passed to `build` in the plot
+    timing_overlay: bool,
+    /// Whether to draw reference lines at x = 0 and y = 0, see [`Plot::with_zero_lines`]
+    zero_lines: (bool, bool),
+    /// Color of the zero lines
+    zero_line_color: ImVec4,
+    /// Custom scales set for axes, as axis, forward and inverse transform
+    axis_transforms: Vec<(Axis, AxisTransform, AxisTransform)>,
+    /// Scales of the X axes, `None` if ImPlot's default linear scale is used
+    x_scales: [Option<AxisScale>; NUMBER_OF_X_AXES],
+    /// Scales of the Y axes, `None` if ImPlot's default linear scale is used
+    y_scales: [Option<AxisScale>; NUMBER_OF_Y_AXES],
+    /// Axes with SI-prefixed tick labels, as axis and unit
+    si_prefix_axes: Vec<(Axis, String)>,
+    /// Axes whose tick labels are formatted with `auto_format`
+    auto_format_axes: Vec<Axis>,
+    /// Whether the user can interact with the plot, independent of `PlotFlags::NO_INPUTS`
+    inputs_enabled: bool,
+    /// Whether the plot ignores inputs and is dimmed like widgets in imgui's disabled blocks
+    disabled: bool,
+    /// Whether the context menus of the X axes can be opened, independent of `AxisFlags::NO_MENUS`
+    x_menus_enabled: [bool; NUMBER_OF_X_AXES],
+    /// Whether the context menus of the Y axes can be opened, independent of `AxisFlags::NO_MENUS`
+    y_menus_enabled: [bool; NUMBER_OF_Y_AXES],
     /// Flags relating to the X axis of the plot TODO(4bb4) make those into bitflags
     x_flags: [AxisFlags; NUMBER_OF_X_AXES],
     /// Flags relating to the each of the Y axes of the plot TODO(4bb4) make those into bitflags
     y_flags: [AxisFlags; NUMBER_OF_Y_AXES],
+    /// Style variables that only apply to this plot. They are pushed before the plot is begun and
+    /// popped again after it has ended.
+    style_var_overrides: Vec<(StyleVar, StyleVarValue)>,
+    /// Style colors that only apply to this plot. They are pushed before the plot is begun and
+    /// popped again after it has ended.
+    style_color_overrides: Vec<(PlotColorElement, ImVec4)>,
+    /// Whether to show crosshairs, independent of `PlotFlags::CROSSHAIRS`
+    crosshairs: bool,
+    /// Whether to constrain panning and zooming of the first X and Y axes to the extents of the
+    /// data plotted on the previous frame
+    clamp_to_data: bool,
+    /// Whether the extents of the plotted data are tracked, see [`Plot::with_data_extents`]
+    track_data_extents: bool,
+    /// Whether the bounds of each plotted item are recorded, see [`Plot::with_item_info`]
+    record_item_bounds: bool,
+    /// Axes whose limits are animated, which have their limits stored at the end of each frame
+    animated_axes: Vec<Axis>,
+    /// Ranges borrowed by stateful limits, which are only stored as pointers in `x_limits` and
+    /// `y_limits` because ImPlot writes to them directly
+    stateful_limits: PhantomData<&'a mut ImPlotRange>,
 }
 
-impl Plot {
+impl<'a> Plot<'a> {
     /// Create a new plot with some defaults set. Does not draw anything yet.
     /// Note that this uses antialiasing by default, unlike the C++ API. If you are seeing
     /// artifacts or weird rendering, try disabling it.
@@ -259,41 +824,262 @@ impl Plot {
         Self {
             title: CString::new(title)
                 .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", title)),
-            size: [DEFAULT_PLOT_SIZE_X, DEFAULT_PLOT_SIZE_Y],
-            x_label: CString::new("").unwrap(),
-            y_label: CString::new("").unwrap(),
+            size: default_plot_size(),
+            x_labels: Default::default(),
+            y_labels: Default::default(),
+            x_enabled: [true, false, false],
+            y_enabled: [true, false, false],
             x_limits: Default::default(),
             y_limits: Default::default(),
             x_tick_positions: [POS_NONE; NUMBER_OF_X_AXES],
             x_tick_labels: [TICK_NONE; NUMBER_OF_X_AXES],
             show_x_default_ticks: [false; NUMBER_OF_X_AXES],
+            x_tick_label_angles: [None; NUMBER_OF_X_AXES],
+            tick_label_max_width: None,
+            x_mirrors: [None; NUMBER_OF_X_AXES],
+            aspect_ratio: None,
             y_tick_positions: [POS_NONE; NUMBER_OF_Y_AXES],
             y_tick_labels: [TICK_NONE; NUMBER_OF_Y_AXES],
+            x_cached_ticks: [None; NUMBER_OF_X_AXES],
+            y_cached_ticks: [None; NUMBER_OF_Y_AXES],
             show_y_default_ticks: [false; NUMBER_OF_Y_AXES],
             legend_configuration: None,
+            max_legend_entries: None,
             plot_flags: PlotFlags::empty(),
+            empty_text: None,
+            timing_overlay: false,
+            zero_lines: (false, false),
+            zero_line_color: ImVec4 { x: 0.5, y: 0.5, z: 0.5, w: 0.5 },
+            axis_transforms: Vec::new(),
+            x_scales: [None; NUMBER_OF_X_AXES],
+            y_scales: [None; NUMBER_OF_Y_AXES],
+            si_prefix_axes: Vec::new(),
+            auto_format_axes: Vec::new(),
+            inputs_enabled: true,
+            disabled: false,
+            x_menus_enabled: [true; NUMBER_OF_X_AXES],
+            y_menus_enabled: [true; NUMBER_OF_Y_AXES],
             x_flags: [AxisFlags::empty(); NUMBER_OF_X_AXES],
             y_flags: [AxisFlags::empty(); NUMBER_OF_Y_AXES],
+            style_var_overrides: Vec::new(),
+            style_color_overrides: Vec::new(),
+            crosshairs: false,
+            clamp_to_data: false,
+            track_data_extents: false,
+            record_item_bounds: false,
+            animated_axes: Vec::new(),
+            stateful_limits: PhantomData,
         }
     }
 
     /// Sets the plot size, given as [size_x, size_y]. Units are the same as
     /// what imgui uses. TODO(4bb4) ... which is? I'm not sure it's pixels
+    ///
+    /// If this is not called, the size set with `Context::set_default_plot_size` is used.
     #[inline]
     pub fn size(mut self, size: [f32; 2]) -> Self {
         self.size = size;
         self
     }
 
+    /// Sets the minimum size the plot frame can be shrunk to, given as [size_x, size_y]. This only
+    /// applies to this plot, as opposed to pushing `StyleVar::PlotMinSize` globally.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotMinSize"))]
+    #[inline]
+    pub fn with_min_size(self, min_size: [f32; 2]) -> Self {
+        self.with_style_var_override(StyleVar::PlotMinSize, min_size)
+    }
+
+    /// Sets the size used for this plot if its size is set to `[0.0, 0.0]` with [`Plot::size`],
+    /// as opposed to pushing `StyleVar::PlotDefaultSize` globally. Note that this has no effect
+    /// otherwise - plots are created with a non-zero default size in this crate.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotDefaultSize"))]
+    #[inline]
+    pub fn with_default_size(self, default_size: [f32; 2]) -> Self {
+        self.with_style_var_override(StyleVar::PlotDefaultSize, default_size)
+    }
+
+    /// Sets the padding between the edge of the plot frame and its contents (title, axes and the
+    /// plot area), given as [x, y]. This only applies to this plot, as opposed to pushing
+    /// `StyleVar::PlotPadding` globally. Use [0, 0] for edge-to-edge plots, for example a
+    /// heatmap filling a panel as a background.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotPadding"))]
+    #[inline]
+    pub fn with_plot_padding(self, padding: [f32; 2]) -> Self {
+        self.with_style_var_override(StyleVar::PlotPadding, padding)
+    }
+
+    /// Sets the padding between the legend and the edge of the plot area, given as [x, y]. This
+    /// only applies to this plot, as opposed to pushing `StyleVar::LegendPadding` globally.
+    #[rustversion::attr(since(1.48), doc(alias = "LegendPadding"))]
+    #[inline]
+    pub fn with_legend_padding(self, padding: [f32; 2]) -> Self {
+        self.with_style_var_override(StyleVar::LegendPadding, padding)
+    }
+
+    /// Sets the padding between the legend frame and its entries, given as [x, y]. This only
+    /// applies to this plot, as opposed to pushing `StyleVar::LegendInnerPadding` globally.
+    #[rustversion::attr(since(1.48), doc(alias = "LegendInnerPadding"))]
+    #[inline]
+    pub fn with_legend_inner_padding(self, padding: [f32; 2]) -> Self {
+        self.with_style_var_override(StyleVar::LegendInnerPadding, padding)
+    }
+
+    /// Sets the spacing between legend entries, given as [x, y]. This only applies to this plot,
+    /// as opposed to pushing `StyleVar::LegendSpacing` globally.
+    #[rustversion::attr(since(1.48), doc(alias = "LegendSpacing"))]
+    #[inline]
+    pub fn with_legend_spacing(self, spacing: [f32; 2]) -> Self {
+        self.with_style_var_override(StyleVar::LegendSpacing, spacing)
+    }
+
+    /// Show or hide the grid lines of the selected axis, including the minor ones drawn between
+    /// the major ones (and at 2 to 9 within each decade on logarithmic axes). This sets or clears
+    /// `AxisFlags::NO_GRID_LINES` for the axis only, so call it after
+    /// [`Plot::with_x_axis_flags`] or [`Plot::with_y_axis_flags`] for that axis.
+    ///
+    /// ImPlot has no flag for the minor grid lines alone: they are shown on every axis that
+    /// shows grid lines, with the global `StyleVar::MinorAlpha`. To only have the minor grid
+    /// lines of a logarithmic axis, hide the grid lines of the other axes with this.
+    #[rustversion::attr(since(1.48), doc(alias = "NoGridLines"))]
+    #[inline]
+    pub fn with_minor_gridlines(mut self, axis: Axis, enabled: bool) -> Self {
+        if let Some(axis_index) = get_x_axis_index(axis) {
+            self.x_flags[axis_index].set(AxisFlags::NO_GRID_LINES, !enabled);
+        } else if let Some(axis_index) = get_y_axis_index(axis) {
+            self.y_flags[axis_index].set(AxisFlags::NO_GRID_LINES, !enabled);
+        }
+        self
+    }
+
+    /// Internal helper to store a style variable that only applies to this plot. Setting the same
+    /// variable again replaces the previous value.
+    fn with_style_var_override<V: Into<StyleVarValue>>(mut self, variable: StyleVar, value: V) -> Self {
+        self.style_var_overrides.retain(|(v, _)| *v as u32 != variable as u32);
+        self.style_var_overrides.push((variable, value.into()));
+        self
+    }
+
+    /// Replace the mouse cursor with crosshairs of the given color while the plot is hovered. This
+    /// adds `PlotFlags::CROSSHAIRS` to the plot flags when the plot is begun, and the color only
+    /// applies to this plot, as opposed to pushing `PlotColorElement::Crosshairs` globally.
+    #[rustversion::attr(since(1.48), doc(alias = "Crosshairs"))]
+    #[inline]
+    pub fn with_crosshairs(mut self, color: ImVec4) -> Self {
+        self.crosshairs = true;
+        self.style_color_overrides
+            .retain(|(element, _)| *element as u32 != PlotColorElement::Crosshairs as u32);
+        self.style_color_overrides.push((PlotColorElement::Crosshairs, color));
+        self
+    }
+
+    /// Keep the user from panning or zooming the axes beyond the extents of the data plotted
+    /// against them, so they cannot scroll into empty space. Each axis is constrained to the data
+    /// plotted against it, and axes without data are not constrained. Which axes data is plotted
+    /// against is followed through [`crate::set_axis`] and [`crate::plot_on_axis`].
+    ///
+    /// The extents of the data are only known once everything has been plotted, but the
+    /// constraints have to be set up before that. Hence the extents of the data plotted on the
+    /// previous frame are used, which means changes in the data take effect with a lag of one
+    /// frame, and nothing is constrained on the very first frame. The extents are stored per
+    /// plot title, so plots using this should have unique titles.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisLimitsConstraints"))]
+    #[inline]
+    pub fn clamp_to_data(mut self) -> Self {
+        self.clamp_to_data = true;
+        self
+    }
+
+    /// Track the extents of the data plotted into this plot, so they can be read with
+    /// [`PlotToken::data_extents`] while plotting, or with [`crate::plot_data_extents`] after
+    /// the plot has ended. Tracking is opt-in since every plotted point has to be looked at. The
+    /// extents are stored per plot title, so plots using this should have unique titles.
+    #[inline]
+    pub fn with_data_extents(mut self) -> Self {
+        self.track_data_extents = true;
+        self
+    }
+
+    /// Record the bounding box of the data of each item plotted into this plot, so it is part of
+    /// the descriptions returned by [`PlotToken::item_info`], for example for accessibility
+    /// overlays or debugging. Labels, element types and point counts are recorded either way,
+    /// but the bounds are opt-in since every plotted point has to be looked at.
+    #[inline]
+    pub fn with_item_info(mut self) -> Self {
+        self.record_item_bounds = true;
+        self
+    }
+
+    /// Draw infinite reference lines at zero on the first axes, a vertical one at x = 0 if `x` is
+    /// set and a horizontal one at y = 0 if `y` is set, for example for signed data. The lines
+    /// follow panning and zooming, are drawn below everything plotted in the plot, and are not
+    /// shown in the legend or taken into account when fitting the plot to its data. They are
+    /// half-transparent gray unless a color is set with [`Plot::with_zero_line_color`].
+    #[rustversion::attr(since(1.48), doc(alias = "PlotInfLines"))]
+    #[inline]
+    pub fn with_zero_lines(mut self, x: bool, y: bool) -> Self {
+        self.zero_lines = (x, y);
+        self
+    }
+
+    /// Set the color of the lines drawn with [`Plot::with_zero_lines`].
+    #[inline]
+    pub fn with_zero_line_color(mut self, color: ImVec4) -> Self {
+        self.zero_line_color = color;
+        self
+    }
+
+    /// Internal helper to draw the zero lines, before anything else is plotted. The color is
+    /// always set explicitly, since automatic colors would take one from the colormap and shift
+    /// the colors of the actual data.
+    fn maybe_draw_zero_lines(&self) {
+        let (x, y) = self.zero_lines;
+        if x {
+            draw_zero_line("##zero line x", self.zero_line_color, 0);
+        }
+        if y {
+            draw_zero_line(
+                "##zero line y",
+                self.zero_line_color,
+                sys::ImPlotInfLinesFlags__ImPlotInfLinesFlags_Horizontal,
+            );
+        }
+    }
+
+    /// Show the given text in the center of the plot if no plot elements were plotted into it
+    /// during a frame, for example "No data" while the data is not available yet. Text plotted
+    /// with `PlotText` does not count as a plot element here.
+    ///
+    /// # Panics
+    /// Will panic if the text contains internal null bytes.
+    #[inline]
+    pub fn with_empty_text(mut self, text: &str) -> Self {
+        self.empty_text = Some(
+            CString::new(text)
+                .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", text)),
+        );
+        self
+    }
+
+    /// Show how long the closure passed to [`Plot::build`] took to run, in milliseconds, in the top
+    /// left corner of the plot. This is meant for finding out which plots are slow, for example
+    /// with large datasets. Only the time spent in the closure is measured, not the time ImPlot
+    /// spends rendering at the end of the plot. With [`Plot::build_with_layers`], the closures
+    /// brought to the front are included. The overlay is not shown when using [`Plot::begin`]
+    /// directly.
+    #[inline]
+    pub fn with_timing_overlay(mut self, timing_overlay: bool) -> Self {
+        self.timing_overlay = timing_overlay;
+        self
+    }
+
     /// Set the x label of the plot
     ///
     /// # Panics
     /// Will panic if the label string contains internal null bytes.
     #[inline]
-    pub fn x_label(mut self, label: &str) -> Self {
-        self.x_label = CString::new(label)
-            .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", label));
-        self
+    pub fn x_label(self, label: &str) -> Self {
+        self.with_x_axis_label(Axis::X1, label)
     }
 
     /// Set the y label of the plot
@@ -301,14 +1087,72 @@ impl Plot {
     /// # Panics
     /// Will panic if the label string contains internal null bytes.
     #[inline]
-    pub fn y_label(mut self, label: &str) -> Self {
-        self.y_label = CString::new(label)
-            .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", label));
+    pub fn y_label(self, label: &str) -> Self {
+        self.with_y_axis_label(Axis::Y1, label)
+    }
+
+    /// Set the label of the given X axis. This also enables the axis if it is not the first one.
+    /// This function requires that the axis value refers to some X axis, otherwise this will be
+    /// a no-op.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    #[inline]
+    pub fn with_x_axis_label(mut self, axis: Axis, label: &str) -> Self {
+        if let Some(axis_index) = get_x_axis_index(axis) {
+            self.x_labels[axis_index] = CString::new(label)
+                .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", label));
+            self.x_enabled[axis_index] = true;
+        }
+        self
+    }
+
+    /// Set the label of the given Y axis. This also enables the axis if it is not the first one.
+    /// This function requires that the axis value refers to some Y axis, otherwise this will be
+    /// a no-op.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    #[inline]
+    pub fn with_y_axis_label(mut self, axis: Axis, label: &str) -> Self {
+        if let Some(axis_index) = get_y_axis_index(axis) {
+            self.y_labels[axis_index] = CString::new(label)
+                .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", label));
+            self.y_enabled[axis_index] = true;
+        }
         self
     }
 
+    /// Set the label of the given X axis for plotting quantities of type `Q` on it, with the unit
+    /// of `Q` appended, as in "Distance [m]". Otherwise this works like
+    /// [`Plot::with_x_axis_label`]. Use it together with plot element methods that take
+    /// quantities, such as [`PlotLine::plot_quantities`](struct.PlotLine.html#method.plot_quantities).
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    #[inline]
+    pub fn with_x_axis_quantity<Q: Quantity>(self, axis: Axis, label: &str) -> Self {
+        self.with_x_axis_label(axis, &quantity_label::<Q>(label))
+    }
+
+    /// Set the label of the given Y axis for plotting quantities of type `Q` on it, with the unit
+    /// of `Q` appended, as in "Speed [m/s]". Otherwise this works like
+    /// [`Plot::with_y_axis_label`].
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    #[inline]
+    pub fn with_y_axis_quantity<Q: Quantity>(self, axis: Axis, label: &str) -> Self {
+        self.with_y_axis_label(axis, &quantity_label::<Q>(label))
+    }
+
     /// Set the x limits of the plot.
     ///
+    /// The condition decides when the limits are applied: `Always` on every frame, `Once` and
+    /// `FirstUseEver` only on the first frame the plot is shown, `Appearing` on every frame the
+    /// plot is shown after not having been shown on the previous one (for example when a collapsed
+    /// header or a tab containing it is opened again), and `Never` not at all.
+    ///
     /// Note: This conflicts with `linked_x_limits`, whichever is called last on plot construction
     /// takes effect.
     #[inline]
@@ -320,6 +1164,7 @@ impl Plot {
     ) -> Self {
         if let Some(axis_index) = get_x_axis_index(axis) {
             self.x_limits[axis_index] = Some(AxisLimitSpecification::Single(limits.into(), condition));
+            self.x_enabled[axis_index] = true;
         }
         self
     }
@@ -345,11 +1190,29 @@ impl Plot {
         self.x_limits(limits, condition, Axis::X3)
     }
 
-    /// Set linked x limits for this plot. Pass clones of the same `Rc` into other plots
-    /// to link their limits with the same values. Call multiple times with different
-    /// `axis` values to set for multiple axes, or use the convenience methods such as
-    /// [`Plot::x1_limits`]. This function requires that the axis value refers to some X axis
-    /// otherwise this will be a no-op
+    /// Set X limits of the plot for the given X axis that are bound to `limits` in both
+    /// directions: the plot shows the limits in `limits`, and when the user pans or zooms, the
+    /// new limits are written back to `limits` when the plot is ended. This is a simpler
+    /// alternative to [`Plot::linked_x_limits`] when the limits do not need to be shared with
+    /// other plots. This function requires that the axis value refers to some X axis, otherwise
+    /// this will be a no-op.
+    ///
+    /// Note: This conflicts with `x_limits` and `linked_x_limits`, whichever is called last on
+    /// plot construction takes effect for a given axis.
+    #[inline]
+    pub fn x_limits_stateful(mut self, limits: &'a mut ImPlotRange, axis: Axis) -> Self {
+        if let Some(axis_index) = get_x_axis_index(axis) {
+            self.x_limits[axis_index] = Some(AxisLimitSpecification::Stateful(limits));
+            self.x_enabled[axis_index] = true;
+        }
+        self
+    }
+
+    /// Set linked x limits for this plot. Pass clones of the same `Rc` into other plots
+    /// to link their limits with the same values. Call multiple times with different
+    /// `axis` values to set for multiple axes, or use the convenience methods such as
+    /// [`Plot::x1_limits`]. This function requires that the axis value refers to some X axis
+    /// otherwise this will be a no-op
     ///
     /// Note: This conflicts with `x_limits`, whichever is called last on plot construction takes
     /// effect.
@@ -361,6 +1224,7 @@ impl Plot {
     ) -> Self {
         if let Some(axis_index) = get_x_axis_index(axis) {
             self.x_limits[axis_index] = Some(AxisLimitSpecification::Linked(limits));
+            self.x_enabled[axis_index] = true;
         }
         self
     }
@@ -391,7 +1255,8 @@ impl Plot {
 
     /// Set the Y limits of the plot for the given Y axis. Call multiple times with different
     /// `y_axis_choice` values to set for multiple axes, or use the convenience methods such as
-    /// [`Plot::y1_limits`].
+    /// [`Plot::y1_limits`]. See [`Plot::x_limits`] for when the limits are applied depending on
+    /// the condition.
     ///
     /// Note: This conflicts with `linked_y_limits`, whichever is called last on plot construction
     /// takes effect for a given axis.
@@ -404,6 +1269,7 @@ impl Plot {
     ) -> Self {
         if let Some(axis_index) = get_y_axis_index(axis) {
             self.y_limits[axis_index] = Some(AxisLimitSpecification::Single(limits.into(), condition));
+            self.y_enabled[axis_index] = true;
         }
         self
     }
@@ -429,6 +1295,24 @@ impl Plot {
         self.y_limits(limits, condition, Axis::Y3)
     }
 
+    /// Set Y limits of the plot for the given Y axis that are bound to `limits` in both
+    /// directions: the plot shows the limits in `limits`, and when the user pans or zooms, the
+    /// new limits are written back to `limits` when the plot is ended. This is a simpler
+    /// alternative to [`Plot::linked_y_limits`] when the limits do not need to be shared with
+    /// other plots. This function requires that the axis value refers to some Y axis, otherwise
+    /// this will be a no-op.
+    ///
+    /// Note: This conflicts with `y_limits` and `linked_y_limits`, whichever is called last on
+    /// plot construction takes effect for a given axis.
+    #[inline]
+    pub fn y_limits_stateful(mut self, limits: &'a mut ImPlotRange, axis: Axis) -> Self {
+        if let Some(axis_index) = get_y_axis_index(axis) {
+            self.y_limits[axis_index] = Some(AxisLimitSpecification::Stateful(limits));
+            self.y_enabled[axis_index] = true;
+        }
+        self
+    }
+
     /// Set linked Y limits of the plot for the given Y axis. Pass clones of the same `Rc` into
     /// other plots to link their limits with the same values. Call multiple times with different
     /// `axis` values to set for multiple axes, or use the convenience methods such as
@@ -444,8 +1328,8 @@ impl Plot {
         axis: Axis,
     ) -> Self {
         if let Some(axis_index) = get_y_axis_index(axis) {
-            let axis_index = axis as usize - Axis::Y1 as usize;
             self.y_limits[axis_index] = Some(AxisLimitSpecification::Linked(limits));
+            self.y_enabled[axis_index] = true;
         }
         self
     }
@@ -474,6 +1358,48 @@ impl Plot {
         self.linked_y_limits(limits, Axis::Y3)
     }
 
+    /// Move the limits of an axis smoothly towards target limits over several frames, instead
+    /// of snapping to them like limits set with `Condition::Always` do. The animation state is
+    /// kept by the caller, see [`LimitsAnimation`]; apply it to the plot on every frame while it
+    /// runs. The animation starts from the limits the axis had on the previous frame, so on the
+    /// first frame it is applied, it only records them, and it starts on the next frame. While it
+    /// runs, its limits override user input on the axis; once it is finished, it no longer sets
+    /// the limits. This replaces other limits set for the axis while the animation runs.
+    #[rustversion::attr(since(1.48), doc(alias = "SetNextAxisLimits"))]
+    pub fn animate_limits(mut self, animation: &mut LimitsAnimation) -> Self {
+        let axis = animation.axis;
+        if let Some(axis_index) = get_x_axis_index(axis) {
+            self.x_enabled[axis_index] = true;
+        } else if let Some(axis_index) = get_y_axis_index(axis) {
+            self.y_enabled[axis_index] = true;
+        }
+        if !self.animated_axes.contains(&axis) {
+            self.animated_axes.push(axis);
+        }
+        if animation.finished {
+            return self;
+        }
+
+        let (from, started) = match animation.start {
+            Some(start) => start,
+            None => match crate::plot_state::previous_frame_axis_limits(&self.title, axis) {
+                Some(from) => {
+                    let start = (from, Instant::now());
+                    animation.start = Some(start);
+                    start
+                }
+                // Wait for the limits of this frame to be stored
+                None => return self,
+            },
+        };
+        let limits = animation.current_limits(from, started);
+        if get_x_axis_index(axis).is_some() {
+            self.x_limits(limits, Condition::Always, axis)
+        } else {
+            self.y_limits(limits, Condition::Always, axis)
+        }
+    }
+
     /// Set X ticks without labels for the plot. The vector contains one label each in
     /// the form of a tuple `(label_position, label_string)`. The `show_default` setting
     /// determines whether the default ticks are also shown.
@@ -486,7 +1412,9 @@ impl Plot {
     ) -> Self {
         if let Some(axis_index) = get_x_axis_index(axis) {
             self.x_tick_positions[axis_index] = Some(ticks.into());
+            self.x_cached_ticks[axis_index] = None;
             self.show_x_default_ticks[axis_index] = show_default;
+            self.x_enabled[axis_index] = true;
         }
         self
     }
@@ -503,7 +1431,9 @@ impl Plot {
     ) -> Self {
         if let Some(axis_index) = get_y_axis_index(axis) {
             self.y_tick_positions[axis_index] = Some(ticks.into());
+            self.y_cached_ticks[axis_index] = None;
             self.show_y_default_ticks[axis_index] = show_default;
+            self.y_enabled[axis_index] = true;
         }
         self
     }
@@ -512,6 +1442,9 @@ impl Plot {
     /// each in the form of a tuple `(label_position, label_string)`. The `show_default`
     /// setting determines whether the default ticks are also shown.
     ///
+    /// The labels are converted for ImPlot on every frame, which allocates for every label. For
+    /// axes with many ticks, use [`Plot::x_ticks_cached`] to convert them once instead.
+    ///
     /// # Panics
     /// Will panic if any of the tick label strings contain internal null bytes.
     #[inline]
@@ -523,6 +1456,7 @@ impl Plot {
     ) -> Self {
         if let Some(axis_index) = get_x_axis_index(axis) {
             self.x_tick_positions[axis_index] = Some(tick_labels.iter().map(|x| x.0).collect());
+            self.x_cached_ticks[axis_index] = None;
             self.x_tick_labels[axis_index] = Some(
                 tick_labels
                     .iter()
@@ -533,6 +1467,7 @@ impl Plot {
                     .collect(),
             );
             self.show_x_default_ticks[axis_index] = show_default;
+            self.x_enabled[axis_index] = true;
         }
         self
     }
@@ -541,6 +1476,9 @@ impl Plot {
     /// each in the form of a tuple `(label_position, label_string)`. The `show_default`
     /// setting determines whether the default ticks are also shown.
     ///
+    /// The labels are converted for ImPlot on every frame, which allocates for every label. For
+    /// axes with many ticks, use [`Plot::y_ticks_cached`] to convert them once instead.
+    ///
     /// # Panics
     /// Will panic if any of the tick label strings contain internal null bytes.
     #[inline]
@@ -552,6 +1490,7 @@ impl Plot {
     ) -> Self {
         if let Some(axis_index) = get_y_axis_index(axis) {
             self.y_tick_positions[axis_index] = Some(tick_labels.iter().map(|x| x.0).collect());
+            self.y_cached_ticks[axis_index] = None;
             self.y_tick_labels[axis_index] = Some(
                 tick_labels
                     .iter()
@@ -562,10 +1501,114 @@ impl Plot {
                     .collect(),
             );
             self.show_y_default_ticks[axis_index] = show_default;
+            self.y_enabled[axis_index] = true;
         }
         self
     }
 
+    /// Set X ticks for the plot from ticks the caller keeps across frames, instead of converting
+    /// them on every frame like [`Plot::x_ticks_with_labels`] does. This is worth it for axes
+    /// with many custom ticks. Rotating the labels with [`Plot::with_x_tick_label_angle`] is not
+    /// supported for these ticks.
+    #[inline]
+    pub fn x_ticks_cached(mut self, axis: Axis, ticks: &'a CachedTicks) -> Self {
+        if let Some(axis_index) = get_x_axis_index(axis) {
+            self.x_cached_ticks[axis_index] = Some(ticks);
+            self.x_tick_positions[axis_index] = None;
+            self.x_tick_labels[axis_index] = None;
+            self.x_enabled[axis_index] = true;
+        }
+        self
+    }
+
+    /// Set Y ticks for the plot from ticks the caller keeps across frames, instead of converting
+    /// them on every frame like [`Plot::y_ticks_with_labels`] does. This is worth it for axes
+    /// with many custom ticks.
+    #[inline]
+    pub fn y_ticks_cached(mut self, axis: Axis, ticks: &'a CachedTicks) -> Self {
+        if let Some(axis_index) = get_y_axis_index(axis) {
+            self.y_cached_ticks[axis_index] = Some(ticks);
+            self.y_tick_positions[axis_index] = None;
+            self.y_tick_labels[axis_index] = None;
+            self.y_enabled[axis_index] = true;
+        }
+        self
+    }
+
+    /// Rotate the tick labels of the given X axis counter-clockwise by the given angle in degrees,
+    /// for example to keep long categorical labels from overlapping.
+    ///
+    /// ImPlot can only draw horizontal tick labels, so when this is set, ImPlot's own tick labels
+    /// are hidden for the axis and the rotated ones are drawn onto the plot's draw list instead.
+    /// This only works for custom ticks set with [`Plot::x_ticks`] or
+    /// [`Plot::x_ticks_with_labels`] on an axis displayed at the bottom of the plot, since the
+    /// positions of ImPlot's automatic ticks are not known to this crate. Labels of ticks outside
+    /// of the plot area are not drawn. The space below the plot is not enlarged for the rotated
+    /// labels, so leave room for them, for example with an X axis label or `StyleVar::PlotPadding`.
+    #[inline]
+    pub fn with_x_tick_label_angle(mut self, axis: Axis, degrees: f32) -> Self {
+        if let Some(axis_index) = get_x_axis_index(axis) {
+            self.x_tick_label_angles[axis_index] = Some(degrees);
+            self.x_enabled[axis_index] = true;
+        }
+        self
+    }
+
+    /// Shorten custom tick labels that are wider than `max_width` pixels, measured with the
+    /// current ImGui font, by cutting them off at the end and appending "...". This keeps long
+    /// labels on categorical axes from overlapping each other and the plot. It applies to labels
+    /// set with [`Plot::x_ticks_with_labels`] and [`Plot::y_ticks_with_labels`] and to category
+    /// ticks from [`PlotBars::plot_categorical`](crate::PlotBars::plot_categorical), on all
+    /// axes. Labels of [`CachedTicks`] and rotated labels (see
+    /// [`Plot::with_x_tick_label_angle`]) are drawn as they are. Labels are measured every frame,
+    /// so for axes with many ticks it can be cheaper to shorten them once beforehand.
+    #[inline]
+    pub fn with_tick_label_max_width(mut self, max_width: f32) -> Self {
+        self.tick_label_max_width = Some(max_width);
+        self
+    }
+
+    /// Internal helper to apply the maximum tick label width to the given labels, if one is set.
+    fn truncated_tick_labels<'l>(&self, labels: &'l [CString]) -> Vec<Cow<'l, CStr>> {
+        labels
+            .iter()
+            .map(|label| {
+                self.tick_label_max_width
+                    .and_then(|max_width| {
+                        truncate_label(&label.to_string_lossy(), max_width, imgui_text_width)
+                    })
+                    // The truncated label is a part of the original one, so it has no null bytes
+                    .map_or(Cow::Borrowed(label.as_c_str()), |truncated| {
+                        Cow::Owned(CString::new(truncated).unwrap())
+                    })
+            })
+            .collect()
+    }
+
+    /// Internal helper to collect the rotated tick labels to draw for all X axes, as tuples of
+    /// axis, angle and `(position, label)` pairs.
+    fn rotated_tick_labels(&self) -> Vec<RotatedTickLabels> {
+        self.x_tick_label_angles
+            .iter()
+            .enumerate()
+            .filter_map(|(k, angle)| {
+                let positions = self.x_tick_positions[k].as_ref()?;
+                let labels = positions
+                    .iter()
+                    .enumerate()
+                    .map(|(i, position)| {
+                        let label = match &self.x_tick_labels[k] {
+                            Some(labels) => labels[i].to_string_lossy().into_owned(),
+                            None => format!("{}", position),
+                        };
+                        (*position, label)
+                    })
+                    .collect();
+                Some((get_x_axis_from_index(k).unwrap(), (*angle)?, labels))
+            })
+            .collect()
+    }
+
     /// Set the plot flags, see the help for `PlotFlags` for what the available flags are
     #[inline]
     pub fn with_plot_flags(mut self, flags: &PlotFlags) -> Self {
@@ -573,11 +1616,50 @@ impl Plot {
         self
     }
 
+    /// Enable or disable all user inputs on the plot, for example based on a runtime toggle. If
+    /// inputs are disabled, `PlotFlags::NO_INPUTS` is added to the plot flags when the plot is
+    /// begun, regardless of the flags set with [`Plot::with_plot_flags`]. Enabling inputs here
+    /// does not remove `NO_INPUTS` if it was set in the plot flags.
+    #[inline]
+    pub fn with_inputs_enabled(mut self, enabled: bool) -> Self {
+        self.inputs_enabled = enabled;
+        self
+    }
+
+    /// Make the plot behave like other widgets inside of imgui's disabled blocks (see
+    /// `Ui::begin_disabled`) if `disabled` is true: it then ignores all user inputs like with
+    /// [`Plot::with_inputs_enabled`], and it is dimmed by drawing the window background color
+    /// over it, with the opacity imgui dims disabled widgets by.
+    ///
+    /// Imgui does not expose whether it is inside of a disabled block through its public API, so
+    /// pass the same condition the disabled block is begun with.
+    #[inline]
+    pub fn respect_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Enable or disable the context menu of the given axis, for example based on a runtime toggle.
+    /// If the menu is disabled, `AxisFlags::NO_MENUS` is added to the axis flags when the plot is
+    /// begun, regardless of the flags set with [`Plot::with_x_axis_flags`] or
+    /// [`Plot::with_y_axis_flags`]. Enabling the menu here does not remove `NO_MENUS` if it was
+    /// set in the axis flags.
+    #[inline]
+    pub fn with_axis_menus_enabled(mut self, axis: Axis, enabled: bool) -> Self {
+        if let Some(axis_index) = get_x_axis_index(axis) {
+            self.x_menus_enabled[axis_index] = enabled;
+        } else if let Some(axis_index) = get_y_axis_index(axis) {
+            self.y_menus_enabled[axis_index] = enabled;
+        }
+        self
+    }
+
     /// Set the axis flags for the X axis in this plot
     #[inline]
     pub fn with_x_axis_flags(mut self, axis: Axis, flags: &AxisFlags) -> Self {
         if let Some(axis_index) = get_x_axis_index(axis) {
             self.x_flags[axis_index] = *flags;
+            self.x_enabled[axis_index] = true;
         }
         self
     }
@@ -587,6 +1669,332 @@ impl Plot {
     pub fn with_y_axis_flags(mut self, axis: Axis, flags: &AxisFlags) -> Self {
         if let Some(axis_index) = get_y_axis_index(axis) {
             self.y_flags[axis_index] = *flags;
+            self.y_enabled[axis_index] = true;
+        }
+        self
+    }
+
+    /// Set the side of the plot the selected X axis is displayed on. This sets or clears
+    /// `AxisFlags::OPPOSITE` for the axis, so call it after [`Plot::with_x_axis_flags`] for that
+    /// axis.
+    #[inline]
+    pub fn with_x_axis_side(mut self, axis: Axis, side: XAxisSide) -> Self {
+        if let Some(axis_index) = get_x_axis_index(axis) {
+            self.x_flags[axis_index].set(AxisFlags::OPPOSITE, side == XAxisSide::Top);
+            self.x_enabled[axis_index] = true;
+        }
+        self
+    }
+
+    /// Set the side of the plot the selected Y axis is displayed on, for example to put the
+    /// second and third Y axes on the right of the plot. This sets or clears
+    /// `AxisFlags::OPPOSITE` for the axis, so call it after [`Plot::with_y_axis_flags`] for that
+    /// axis.
+    #[inline]
+    pub fn with_y_axis_side(mut self, axis: Axis, side: YAxisSide) -> Self {
+        if let Some(axis_index) = get_y_axis_index(axis) {
+            self.y_flags[axis_index].set(AxisFlags::OPPOSITE, side == YAxisSide::Right);
+            self.y_enabled[axis_index] = true;
+        }
+        self
+    }
+
+    /// Make the selected X axis show the same range as the first X axis, converted to different
+    /// units as `scale * x + offset`. This is useful to label the same data with two units at
+    /// once, for example sample indices on the first X axis and time on a second one, where
+    /// `scale` is the sampling interval and `offset` the time of the first sample. Plot the data
+    /// against the first X axis.
+    ///
+    /// The limits of the first X axis are only known after the plot was set up, so the selected
+    /// axis follows the first one with a delay of one frame, and the selected axis cannot be
+    /// panned or zoomed by itself. `scale` must not be zero.
+    #[inline]
+    pub fn with_x_axis_mirror(mut self, axis: Axis, scale: f64, offset: f64) -> Self {
+        if let Some(axis_index) = get_x_axis_index(axis).filter(|index| *index != 0) {
+            self.x_mirrors[axis_index] = Some((scale, offset));
+            self.x_enabled[axis_index] = true;
+        }
+        self
+    }
+
+    /// Lock the aspect ratio of the first X and Y axes to the given value, so one unit on the Y
+    /// axis is `y_over_x` times as tall as one unit on the X axis is wide. A ratio of 1 gives the
+    /// same result as `PlotFlags::EQUAL`, which should not be combined with this. The Y limits
+    /// are derived from the X limits and the size of the plot area, keeping the center of the Y
+    /// range where it was.
+    ///
+    /// The X limits and plot size are only known after the plot was set up, so the Y limits
+    /// follow them with a delay of one frame, and nothing is enforced on the very first frame.
+    /// The Y axis cannot be panned or zoomed by the user, and zooming the plot only zooms the X
+    /// axis, which the Y axis then follows. Y limits set with [`Plot::y_limits`] are overridden.
+    /// The view is stored per plot title, so plots using this should have unique titles.
+    ///
+    /// # Panics
+    /// Will panic if `y_over_x` is not a positive finite number.
+    #[inline]
+    pub fn with_aspect_ratio(mut self, y_over_x: f64) -> Self {
+        assert!(
+            y_over_x > 0.0 && y_over_x.is_finite(),
+            "Aspect ratio has to be positive and finite: {}",
+            y_over_x
+        );
+        self.aspect_ratio = Some(y_over_x);
+        self
+    }
+
+    /// Internal helper to set the limits of the first Y axis to keep the aspect ratio, based on
+    /// the view the plot had on the previous frame.
+    fn maybe_set_aspect_limits(&self) {
+        let y_over_x = match self.aspect_ratio {
+            Some(y_over_x) => y_over_x,
+            None => return,
+        };
+        let (limits, size) = match crate::plot_state::previous_view(&self.title) {
+            Some(view) if view.1.x > 0.0 && view.1.y > 0.0 => view,
+            _ => return,
+        };
+        // Pixels per unit are size / range on both axes, so the Y range follows from
+        // size.y / y_range = y_over_x * size.x / x_range
+        let x_range = (limits.X.Max - limits.X.Min).abs();
+        let y_range = x_range * f64::from(size.y) / (y_over_x * f64::from(size.x));
+        let y_center = (limits.Y.Min + limits.Y.Max) / 2.0;
+        unsafe {
+            sys::ImPlot_SetNextAxisLimits(
+                Axis::Y1 as i32,
+                y_center - y_range / 2.0,
+                y_center + y_range / 2.0,
+                Condition::Always as sys::ImGuiCond,
+            );
+        }
+    }
+
+    /// Internal helper to set the limits of X axes that mirror the first X axis, based on the
+    /// limits the first X axis had on the previous frame.
+    fn maybe_set_mirrored_limits(&self) {
+        let limits = match crate::plot_state::previous_x_limits(&self.title) {
+            Some(limits) => limits,
+            None => return,
+        };
+        self.x_mirrors
+            .iter()
+            .enumerate()
+            .for_each(|(k, mirror)| {
+                if let Some((scale, offset)) = mirror {
+                    let (a, b) = (scale * limits.Min + offset, scale * limits.Max + offset);
+                    unsafe {
+                        sys::ImPlot_SetNextAxisLimits(
+                            get_x_axis_from_index(k).unwrap() as i32,
+                            a.min(b),
+                            a.max(b),
+                            Condition::Always as sys::ImGuiCond,
+                        );
+                    }
+                }
+            });
+    }
+
+    /// Give the selected axis a custom scale, such as a decibel or Mercator scale, instead of a
+    /// linear one. `forward` maps plot coordinates to the scale's linear space, and `inverse` maps
+    /// back. Both functions must be strictly monotonic over the plotted range and inverses of each
+    /// other, otherwise ticks, panning and zooming will misbehave. A panic in either function is
+    /// re-raised when the plot is ended.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisScale"))]
+    #[inline]
+    pub fn with_axis_transform(
+        mut self,
+        axis: Axis,
+        forward: AxisTransform,
+        inverse: AxisTransform,
+    ) -> Self {
+        if let Some(axis_index) = get_x_axis_index(axis) {
+            self.x_enabled[axis_index] = true;
+        } else if let Some(axis_index) = get_y_axis_index(axis) {
+            self.y_enabled[axis_index] = true;
+        }
+        self.axis_transforms.retain(|(a, _, _)| *a != axis);
+        self.axis_transforms.push((axis, forward, inverse));
+        self
+    }
+
+    /// Set the scale of the selected axis, for example a logarithmic one. This replaces a custom
+    /// scale set with [`Plot::with_axis_transform`] for the same axis.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisScale"))]
+    #[inline]
+    pub fn with_axis_scale(mut self, axis: Axis, scale: AxisScale) -> Self {
+        if let Some(axis_index) = get_x_axis_index(axis) {
+            self.x_scales[axis_index] = Some(scale);
+            self.x_enabled[axis_index] = true;
+        } else if let Some(axis_index) = get_y_axis_index(axis) {
+            self.y_scales[axis_index] = Some(scale);
+            self.y_enabled[axis_index] = true;
+        }
+        self.axis_transforms.retain(|(a, _, _)| *a != axis);
+        self
+    }
+
+    /// Restore the zoom and pan state captured with [`PlotToken::capture_viewport`], by setting
+    /// the limits and scales of all axes in the viewport. The limits are set unconditionally, so
+    /// only call this on the frame the viewport should be restored on, otherwise the user cannot
+    /// pan or zoom the plot.
+    #[inline]
+    pub fn with_viewport(mut self, viewport: &Viewport) -> Self {
+        for k in 0..NUMBER_OF_X_AXES {
+            if let (Some(limits), Some(axis)) = (viewport.x_limits[k], get_x_axis_from_index(k)) {
+                self = self
+                    .x_limits(limits, Condition::Always, axis)
+                    .with_axis_scale(axis, viewport.x_scales[k]);
+            }
+        }
+        for k in 0..NUMBER_OF_Y_AXES {
+            if let (Some(limits), Some(axis)) = (viewport.y_limits[k], get_y_axis_from_index(k)) {
+                self = self
+                    .y_limits(limits, Condition::Always, axis)
+                    .with_axis_scale(axis, viewport.y_scales[k]);
+            }
+        }
+        self
+    }
+
+    /// Internal helper to set up the axis scales. Custom scales are set up separately, see
+    /// [`Plot::setup_axis_transforms`].
+    fn setup_axis_scales(&self) {
+        let x_scales = self.x_scales.iter().enumerate().filter_map(|(k, scale)| {
+            Some((get_x_axis_from_index(k)?, (*scale)?))
+        });
+        let y_scales = self.y_scales.iter().enumerate().filter_map(|(k, scale)| {
+            Some((get_y_axis_from_index(k)?, (*scale)?))
+        });
+        x_scales.chain(y_scales).for_each(|(axis, scale)| unsafe {
+            sys::ImPlot_SetupAxisScale_PlotScale(axis as i32, scale as sys::ImPlotScale);
+        });
+    }
+
+    /// Internal helper to build the viewport to fill in with the limits when it is captured,
+    /// with `Some` limits for the axes the plot uses.
+    fn viewport_template(&self) -> Viewport {
+        let mut viewport = Viewport {
+            x_limits: [None; NUMBER_OF_X_AXES],
+            y_limits: [None; NUMBER_OF_Y_AXES],
+            x_scales: [AxisScale::Linear; NUMBER_OF_X_AXES],
+            y_scales: [AxisScale::Linear; NUMBER_OF_Y_AXES],
+        };
+        for k in 0..NUMBER_OF_X_AXES {
+            if self.x_enabled[k] {
+                viewport.x_limits[k] = Some([0.0, 0.0]);
+            }
+            viewport.x_scales[k] = self.x_scales[k].unwrap_or(AxisScale::Linear);
+        }
+        for k in 0..NUMBER_OF_Y_AXES {
+            if self.y_enabled[k] {
+                viewport.y_limits[k] = Some([0.0, 0.0]);
+            }
+            viewport.y_scales[k] = self.y_scales[k].unwrap_or(AxisScale::Linear);
+        }
+        viewport
+    }
+
+    /// Internal helper to set up the custom axis scales. ImPlot keeps calling the transforms until
+    /// the plot is ended, so the returned callback data has to be kept alive until then.
+    // ImPlot keeps pointers to the callback data, so each one is boxed to keep it in place
+    #[allow(clippy::vec_box)]
+    fn setup_axis_transforms(&self) -> Vec<Box<AxisTransformData>> {
+        self.axis_transforms
+            .iter()
+            .map(|(axis, forward, inverse)| {
+                let mut data = Box::new(CallbackData::new((*forward, *inverse)));
+                unsafe {
+                    sys::ImPlot_SetupAxisScale_PlotTransform(
+                        *axis as i32,
+                        Some(forward_transform_trampoline::<AxisTransform, AxisTransform>),
+                        Some(inverse_transform_trampoline::<AxisTransform, AxisTransform>),
+                        data.as_user_data(),
+                    );
+                }
+                data
+            })
+            .collect()
+    }
+
+    /// Label the ticks of the selected axis in engineering notation with SI prefixes, followed
+    /// by the given unit, for example "1.5kHz" for 1500 with unit "Hz", or "20m" for 0.02 with an
+    /// empty unit. Prefixes from yocto (10^-24) to yotta (10^24) are used, and zero is labeled
+    /// without a prefix.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisFormat"))]
+    #[inline]
+    pub fn with_si_prefix_axis(mut self, axis: Axis, unit: &str) -> Self {
+        if let Some(axis_index) = get_x_axis_index(axis) {
+            self.x_enabled[axis_index] = true;
+        } else if let Some(axis_index) = get_y_axis_index(axis) {
+            self.y_enabled[axis_index] = true;
+        }
+        self.si_prefix_axes.retain(|(a, _)| *a != axis);
+        self.si_prefix_axes.push((axis, unit.to_string()));
+        self.auto_format_axes.retain(|a| *a != axis);
+        self
+    }
+
+    /// Label the ticks of the selected axis with [`auto_format`], which switches between fixed
+    /// and scientific notation depending on the magnitude of the values. ImPlot formats the
+    /// mouse position readout with the tick label format, so this applies to the readout as
+    /// well, which keeps it readable for data spanning many orders of magnitude. This replaces
+    /// SI-prefixed labels set for the axis with [`Plot::with_si_prefix_axis`], and vice versa.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisFormat"))]
+    #[inline]
+    pub fn with_auto_format_axis(mut self, axis: Axis) -> Self {
+        if let Some(axis_index) = get_x_axis_index(axis) {
+            self.x_enabled[axis_index] = true;
+        } else if let Some(axis_index) = get_y_axis_index(axis) {
+            self.y_enabled[axis_index] = true;
+        }
+        self.si_prefix_axes.retain(|(a, _)| *a != axis);
+        self.auto_format_axes.retain(|a| *a != axis);
+        self.auto_format_axes.push(axis);
+        self
+    }
+
+    /// Internal helper to set up the custom tick label formats. ImPlot keeps calling the
+    /// formatters until the plot is ended, so the returned callback data has to be kept alive
+    /// until then.
+    // ImPlot keeps pointers to the callback data, so each one is boxed to keep it in place
+    #[allow(clippy::vec_box)]
+    fn setup_axis_formatters(&self) -> Vec<Box<AxisFormatterData>> {
+        for axis in &self.auto_format_axes {
+            unsafe {
+                sys::ImPlot_SetupAxisFormat_PlotFormatter(
+                    *axis as i32,
+                    Some(auto_formatter),
+                    std::ptr::null_mut(),
+                );
+            }
+        }
+        self.si_prefix_axes
+            .iter()
+            .map(|(axis, unit)| {
+                let unit = unit.clone();
+                let formatter: AxisFormatter =
+                    Box::new(move |value| format_si_prefixed(value, &unit));
+                let mut data = Box::new(CallbackData::new(formatter));
+                unsafe {
+                    sys::ImPlot_SetupAxisFormat_PlotFormatter(
+                        *axis as i32,
+                        Some(formatter_trampoline::<AxisFormatter>),
+                        data.as_user_data(),
+                    );
+                }
+                data
+            })
+            .collect()
+    }
+
+    /// Make the selected Y axis continuously fit to the data that is within the visible range of
+    /// the X axis, for example to have the Y axis rescale to what is visible while panning
+    /// through a time series. This adds `AxisFlags::AUTO_FIT` and `AxisFlags::RANGE_FIT` to the
+    /// flags of the axis, so call it after [`Plot::with_y_axis_flags`] for that axis.
+    #[inline]
+    pub fn with_y_range_fit(mut self, axis: Axis) -> Self {
+        if let Some(axis_index) = get_y_axis_index(axis) {
+            self.y_flags[axis_index] |= AxisFlags::AUTO_FIT | AxisFlags::RANGE_FIT;
+            self.y_enabled[axis_index] = true;
         }
         self
     }
@@ -603,8 +2011,28 @@ impl Plot {
         self
     }
 
-    /// Internal helper function to set axis limits in case they are specified.
-    fn maybe_set_axis_limits(&self) {
+    /// Only list the first `max_entries` items plotted into this plot in the legend. If more
+    /// items are plotted, the legend ends with a "+K more" entry telling how many were left out.
+    ///
+    /// ImPlot has no way of limiting its legend, so this works by prefixing the labels of the
+    /// items past the limit with `##` before passing them to ImPlot. That has some downsides:
+    /// - Items left out of the legend cannot be hidden or highlighted through it.
+    /// - Which items are left out depends on the order they are plotted in.
+    /// - To ImPlot, an item whose label changes is a different item. If an item moves into or
+    ///   out of the legend, for example because another item stopped being plotted, it loses
+    ///   its hidden state and may get a different color from the colormap.
+    ///
+    /// Items whose labels start with `##` are not listed in the legend anyway, and do not count
+    /// towards the limit. [`PlotToken::items`] still lists all other items.
+    #[inline]
+    pub fn with_max_legend_entries(mut self, max_entries: usize) -> Self {
+        self.max_legend_entries = Some(max_entries);
+        self
+    }
+
+    /// Internal helper function to set axis limits in case they are specified. `appearing` tells
+    /// whether the plot was not shown on the previous frame, for `Condition::Appearing`.
+    fn maybe_set_axis_limits(&self, appearing: bool) {
         // Limit-setting can either happen via direct limits or through linked limits. The version
         // of implot we link to here has different APIs for the two (separate per-axis calls for
         // direct, and one call for everything together for linked), hence the code here is a bit
@@ -616,13 +2044,15 @@ impl Plot {
             .enumerate()
             .for_each(|(k, limit_spec)| {
                 if let Some(AxisLimitSpecification::Single(limits, condition)) = limit_spec {
-                    unsafe {
-                        sys::ImPlot_SetNextAxisLimits(
-                            get_x_axis_from_index(k).unwrap() as i32,
-                            limits.Min,
-                            limits.Max,
-                            *condition as sys::ImGuiCond,
-                        );
+                    if let Some(condition) = limit_condition(*condition, appearing) {
+                        unsafe {
+                            sys::ImPlot_SetNextAxisLimits(
+                                get_x_axis_from_index(k).unwrap() as i32,
+                                limits.Min,
+                                limits.Max,
+                                condition,
+                            );
+                        }
                     }
                 }
             });
@@ -632,13 +2062,15 @@ impl Plot {
             .enumerate()
             .for_each(|(k, limit_spec)| {
                 if let Some(AxisLimitSpecification::Single(limits, condition)) = limit_spec {
-                    unsafe {
-                        sys::ImPlot_SetNextAxisLimits(
-                            get_y_axis_from_index(k).unwrap() as i32,
-                            limits.Min,
-                            limits.Max,
-                            *condition as sys::ImGuiCond,
-                        );
+                    if let Some(condition) = limit_condition(*condition, appearing) {
+                        unsafe {
+                            sys::ImPlot_SetNextAxisLimits(
+                                get_y_axis_from_index(k).unwrap() as i32,
+                                limits.Min,
+                                limits.Max,
+                                condition,
+                            );
+                        }
                     }
                 }
             });
@@ -648,14 +2080,18 @@ impl Plot {
             .x_limits
             .iter()
             .map(|limit_spec| {
-                if let Some(AxisLimitSpecification::Linked(value)) = limit_spec {
-                    let mut borrowed = value.borrow_mut();
-                    (
-                        &mut (*borrowed).Min as *mut _,
-                        &mut (*borrowed).Max as *mut _,
-                    )
-                } else {
-                    (std::ptr::null_mut(), std::ptr::null_mut())
+                match limit_spec {
+                    Some(AxisLimitSpecification::Linked(value)) => {
+                        let mut borrowed = value.borrow_mut();
+                        (
+                            &mut (*borrowed).Min as *mut _,
+                            &mut (*borrowed).Max as *mut _,
+                        )
+                    }
+                    Some(AxisLimitSpecification::Stateful(value)) => unsafe {
+                        (&mut (**value).Min as *mut _, &mut (**value).Max as *mut _)
+                    },
+                    _ => (std::ptr::null_mut(), std::ptr::null_mut()),
                 }
             })
             .collect();
@@ -664,14 +2100,18 @@ impl Plot {
             .y_limits
             .iter()
             .map(|limit_spec| {
-                if let Some(AxisLimitSpecification::Linked(value)) = limit_spec {
-                    let mut borrowed = value.borrow_mut();
-                    (
-                        &mut (*borrowed).Min as *mut _,
-                        &mut (*borrowed).Max as *mut _,
-                    )
-                } else {
-                    (std::ptr::null_mut(), std::ptr::null_mut())
+                match limit_spec {
+                    Some(AxisLimitSpecification::Linked(value)) => {
+                        let mut borrowed = value.borrow_mut();
+                        (
+                            &mut (*borrowed).Min as *mut _,
+                            &mut (*borrowed).Max as *mut _,
+                        )
+                    }
+                    Some(AxisLimitSpecification::Stateful(value)) => unsafe {
+                        (&mut (**value).Min as *mut _, &mut (**value).Max as *mut _)
+                    },
+                    _ => (std::ptr::null_mut(), std::ptr::null_mut()),
                 }
             })
             .collect();
@@ -679,7 +2119,9 @@ impl Plot {
         unsafe {
             // Calling this unconditionally here as calling it with all NULL pointers should not
             // affect anything. In terms of unsafety, the pointers should be OK as long as any plot
-            // struct that has an Rc to the same data is alive.
+            // struct that has an Rc to the same data is alive. Stateful limits are borrowed for
+            // the lifetime of the plot and its token, so they outlive the EndPlot call that
+            // writes them back.
             for (i, p) in x_limit_pointers.iter().enumerate() {
                 sys::ImPlot_SetNextAxisLinks(get_x_axis_from_index(i).unwrap() as i32, p.0, p.1 );
             }
@@ -689,10 +2131,86 @@ impl Plot {
         }
     }
 
+    /// Internal helper function to set up all enabled axes. Axes that are not set up here are
+    /// hidden by ImPlot and cannot have items plotted on them.
+    fn setup_axes(&self) {
+        self.x_enabled
+            .iter()
+            .enumerate()
+            .filter(|(_, enabled)| **enabled)
+            .for_each(|(k, _)| {
+                let mut flags = self.x_flags[k];
+                if self.x_tick_label_angles[k].is_some() {
+                    // The rotated labels are drawn by us in PlotToken::end()
+                    flags |= AxisFlags::NO_TICK_LABELS;
+                }
+                if !self.x_menus_enabled[k] {
+                    flags |= AxisFlags::NO_MENUS;
+                }
+                unsafe {
+                    sys::ImPlot_SetupAxis(
+                        get_x_axis_from_index(k).unwrap() as i32,
+                        self.x_labels[k].as_ptr(),
+                        flags.bits() as i32,
+                    );
+                }
+            });
+
+        self.y_enabled
+            .iter()
+            .enumerate()
+            .filter(|(_, enabled)| **enabled)
+            .for_each(|(k, _)| {
+                let mut flags = self.y_flags[k];
+                if !self.y_menus_enabled[k] {
+                    flags |= AxisFlags::NO_MENUS;
+                }
+                unsafe {
+                    sys::ImPlot_SetupAxis(
+                        get_y_axis_from_index(k).unwrap() as i32,
+                        self.y_labels[k].as_ptr(),
+                        flags.bits() as i32,
+                    );
+                }
+            });
+    }
+
     /// Internal helper function to set tick labels in case they are specified. This does the
     /// preparation work that is the same for both the X and Y axis plots, then calls the
     /// "set next plot ticks" wrapper functions for both X and Y.
     fn maybe_set_tick_labels(&self) {
+        // Category ticks stored by PlotBars::plot_categorical on the previous frame, unless the
+        // axis has custom ticks already
+        crate::plot_state::take_category_ticks(&self.title)
+            .into_iter()
+            .filter(|(axis, _)| !self.has_custom_ticks(*axis))
+            .for_each(|(axis, labels)| {
+                let positions: Vec<f64> = (0..labels.len()).map(|i| i as f64).collect();
+                let labels = self.truncated_tick_labels(&labels);
+                let mut pointers: Vec<*const c_char> = labels.iter().map(|x| x.as_ptr()).collect();
+                unsafe {
+                    sys::ImPlot_SetupAxisTicks_doublePtr(
+                        axis as i32,
+                        positions.as_ptr(),
+                        positions.len() as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                        pointers.as_mut_ptr(),
+                        false,
+                    )
+                }
+            });
+
+        // Ticks kept by the caller are ready to be passed on as they are
+        self.x_cached_ticks
+            .iter()
+            .enumerate()
+            .filter_map(|(k, ticks)| Some((get_x_axis_from_index(k)?, (*ticks)?)))
+            .chain(
+                self.y_cached_ticks
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(k, ticks)| Some((get_y_axis_from_index(k)?, (*ticks)?))),
+            )
+            .for_each(|(axis, ticks)| ticks.setup(axis));
 
         // Show x ticks if they are available
         self.x_tick_positions
@@ -702,10 +2220,12 @@ impl Plot {
             .enumerate()
             .for_each(|(k, ((positions, labels), show_defaults))| {
                 if positions.is_some() && !positions.as_ref().unwrap().is_empty() {
-                    // The vector of pointers we create has to have a longer lifetime
+                    // The vectors of labels and pointers we create have to have a longer lifetime
+                    let truncated_labels;
                     let mut pointer_vec;
                     let labels_pointer = if let Some(labels_value) = &labels {
-                        pointer_vec = labels_value
+                        truncated_labels = self.truncated_tick_labels(labels_value);
+                        pointer_vec = truncated_labels
                             .iter()
                             .map(|x| x.as_ptr() as *const c_char)
                             .collect::<Vec<*const c_char>>();
@@ -733,10 +2253,12 @@ impl Plot {
             .enumerate()
             .for_each(|(k, ((positions, labels), show_defaults))| {
                 if positions.is_some() && !positions.as_ref().unwrap().is_empty() {
-                    // The vector of pointers we create has to have a longer lifetime
+                    // The vectors of labels and pointers we create have to have a longer lifetime
+                    let truncated_labels;
                     let mut pointer_vec;
                     let labels_pointer = if let Some(labels_value) = &labels {
-                        pointer_vec = labels_value
+                        truncated_labels = self.truncated_tick_labels(labels_value);
+                        pointer_vec = truncated_labels
                             .iter()
                             .map(|x| x.as_ptr() as *const c_char)
                             .collect::<Vec<*const c_char>>();
@@ -758,6 +2280,17 @@ impl Plot {
             });
     }
 
+    /// Internal helper to check whether custom ticks are set for the given axis.
+    fn has_custom_ticks(&self, axis: Axis) -> bool {
+        if let Some(axis_index) = get_x_axis_index(axis) {
+            self.x_tick_positions[axis_index].is_some() || self.x_cached_ticks[axis_index].is_some()
+        } else if let Some(axis_index) = get_y_axis_index(axis) {
+            self.y_tick_positions[axis_index].is_some() || self.y_cached_ticks[axis_index].is_some()
+        } else {
+            false
+        }
+    }
+
     /// Attempt to show the plot. If this returns a token, the plot will actually
     /// be drawn. In this case, use the drawing functionality to draw things on the
     /// plot, and then call `end()` on the token when done with the plot.
@@ -765,21 +2298,82 @@ impl Plot {
     ///
     /// For a convenient implementation of all this, use [`build()`](struct.Plot.html#method.build)
     /// instead.
+    ///
+    /// # Panics
+    /// In debug builds, this panics if no imgui frame is active, i.e. if it is called outside of
+    /// `new_frame()` and `render()` of the imgui context, instead of letting ImPlot fail an
+    /// assertion with a message that does not point at the mistake.
     #[rustversion::attr(since(1.48), doc(alias = "BeginPlot"))]
-    pub fn begin(&self, plot_ui: &PlotUi) -> Option<PlotToken> {
-        self.maybe_set_axis_limits();
-        self.maybe_set_tick_labels();
+    pub fn begin(&self, plot_ui: &PlotUi) -> Option<PlotToken<'a>> {
+        debug_assert!(
+            imgui_frame_active(),
+            "Plot \"{}\" was begun outside of an imgui frame. Plots have to be built between \
+             starting a frame with new_frame() and rendering it with render() on the imgui context.",
+            self.title.to_string_lossy()
+        );
+        let appearing = crate::plot_state::mark_shown(&self.title);
+        self.maybe_set_axis_limits(appearing);
+        self.maybe_set_mirrored_limits();
+        self.maybe_set_aspect_limits();
+        let style_var_tokens: Vec<StyleVarToken> = self
+            .style_var_overrides
+            .iter()
+            .map(|(variable, value)| match value {
+                StyleVarValue::F32(value) => push_style_var_f32(variable, *value),
+                StyleVarValue::Vec2(value) => push_style_var_imvec2(variable, *value),
+            })
+            .collect();
+        let style_color_tokens: Vec<StyleColorToken> = self
+            .style_color_overrides
+            .iter()
+            .map(|(element, color)| push_style_color(element, color.x, color.y, color.z, color.w))
+            .collect();
+        let disabled = self.disabled;
         let should_render = unsafe {
             let size_vec: ImVec2 = ImVec2 { x: self.size[0], y: self.size[1], };
-            sys::ImPlot_BeginPlot( self.title.as_ptr(),  size_vec,  self.plot_flags.bits() as i32 )
+            let mut plot_flags = self.plot_flags;
+            if !self.inputs_enabled || disabled {
+                plot_flags |= PlotFlags::NO_INPUTS;
+            }
+            if self.crosshairs {
+                plot_flags |= PlotFlags::CROSSHAIRS;
+            }
+            sys::ImPlot_BeginPlot( self.title.as_ptr(),  size_vec,  plot_flags.bits() as i32 )
         };
 
         if should_render {
-            unsafe {
-                sys::ImPlot_SetupAxis(crate::Axis::X1 as i32, self.x_label.as_ptr(), self.x_flags[0].bits() as i32);
-                sys::ImPlot_SetupAxis(crate::Axis::Y1 as i32, self.y_label.as_ptr(), self.y_flags[0].bits() as i32);
-                // sys::ImPlot_SetupAxis(crate::Axis::Y2 as i32, self.y_label.as_ptr(), self.y_flags[1].bits() as i32);
-                // sys::ImPlot_SetupAxis(crate::Axis::Y3 as i32, self.y_label.as_ptr(), self.y_flags[2].bits() as i32);
+            crate::note_pushed(crate::Stack::Plots);
+            // BeginPlot added the plot frame as the last imgui item
+            let frame = crate::plot_state::store_current_frame();
+            let disabled_frame = if disabled { Some(frame) } else { None };
+            // The axis setup calls have to happen after BeginPlot, and ticks can only be set up
+            // on axes that were set up already.
+            self.setup_axes();
+            self.setup_axis_scales();
+            let axis_transforms = self.setup_axis_transforms();
+            let axis_formatters = self.setup_axis_formatters();
+            self.maybe_set_tick_labels();
+            crate::items::begin_recording(self.record_item_bounds, self.max_legend_entries);
+            if self.clamp_to_data {
+                let ranges = crate::extents::previous_extents(&self.title)
+                    .map_or_else(Vec::new, |extents| extents.axis_ranges());
+                // Only axes that had data plotted against them are constrained, to their data
+                for (axis, range) in ranges {
+                    let enabled = match (get_x_axis_index(axis), get_y_axis_index(axis)) {
+                        (Some(index), _) => self.x_enabled[index],
+                        (_, Some(index)) => self.y_enabled[index],
+                        _ => false,
+                    };
+                    if enabled {
+                        unsafe {
+                            sys::ImPlot_SetupAxisLimitsConstraints(axis as i32, range.Min, range.Max);
+                        }
+                    }
+                }
+            }
+            let tracks_extents = self.clamp_to_data || self.track_data_extents;
+            if tracks_extents {
+                crate::extents::begin_tracking();
             }
             // Configure legend location, if one was set. This has to be called between begin() and
             // end(), but since only the last call to it actually affects the outcome, I'm adding
@@ -793,14 +2387,33 @@ impl Plot {
                 let flags: LegendFlags = legend_config.1;
                 unsafe { sys::ImPlot_SetupLegend(location as i32, flags.bits() as i32) }
             }
+            // Plotting anything ends the setup phase, so this has to come after all setup calls
+            self.maybe_draw_zero_lines();
+            // Checking whether the plot is hovered locks the setup, so it has to come last
+            crate::plot_state::update_active_plot(&self.title);
 
             Some(PlotToken {
                 context: plot_ui.context,
                 plot_title: self.title.clone(),
+                style_var_tokens,
+                style_color_tokens,
+                rotated_tick_labels: self.rotated_tick_labels(),
+                tracks_extents,
+                stores_x_limits: self.x_mirrors.iter().any(Option::is_some),
+                stores_view: self.aspect_ratio.is_some(),
+                axis_transforms,
+                axis_formatters,
+                empty_text: self.empty_text.clone(),
+                viewport_template: self.viewport_template(),
+                animated_axes: self.animated_axes.clone(),
+                disabled_frame,
+                stateful_limits: PhantomData,
             })
         } else {
             // In contrast with imgui windows, end() does not have to be
             // called if we don't render. This is more like an imgui popup modal.
+            style_var_tokens.into_iter().rev().for_each(StyleVarToken::pop);
+            style_color_tokens.into_iter().rev().for_each(StyleColorToken::pop);
             None
         }
     }
@@ -808,35 +2421,249 @@ impl Plot {
     /// Creates a window and runs a closure to construct the contents. This internally
     /// calls `begin` and `end`.
     ///
+    /// Plot elements are drawn in the order they are plotted in, so elements plotted later are
+    /// drawn on top of earlier ones. ImPlot has no other way of controlling the draw order, see
+    /// [`Plot::build_with_layers`] for plotting elements on top regardless of call order.
+    ///
     /// Note: the closure is not called if ImPlot::BeginPlot() returned
     /// false - TODO(4bb4) figure out if this is if things are not rendered
     #[rustversion::attr(since(1.48), doc(alias = "BeginPlot"))]
     #[rustversion::attr(since(1.48), doc(alias = "EndPlot"))]
     pub fn build<F: FnOnce()>(self, plot_ui: &PlotUi, f: F) {
         if let Some(token) = self.begin(plot_ui) {
-            f();
+            self.timed(f);
+            token.end()
+        }
+    }
+
+    /// Like [`Plot::build`], but the closure gets a [`PlotLayers`] to defer plotting of some
+    /// elements to after the closure has run, so they are drawn on top of everything else. This
+    /// is useful for highlighting one series among many faint ones when the highlighted one is
+    /// not known until the others are being plotted.
+    pub fn build_with_layers<'l, F: FnOnce(&mut PlotLayers<'l>)>(self, plot_ui: &PlotUi, f: F) {
+        if let Some(token) = self.begin(plot_ui) {
+            self.timed(|| {
+                let mut layers = PlotLayers { front: Vec::new() };
+                f(&mut layers);
+                layers.front.into_iter().for_each(|plot_front| plot_front());
+            });
             token.end()
         }
     }
+
+    /// Internal helper to run the plotting of the build methods, and to show how long it took if
+    /// the timing overlay is enabled, see [`Plot::with_timing_overlay`].
+    fn timed<F: FnOnce()>(&self, f: F) {
+        if !self.timing_overlay {
+            f();
+            return;
+        }
+        let start = Instant::now();
+        f();
+        let elapsed = start.elapsed();
+        draw_corner_text(&format!("{:.3} ms", elapsed.as_secs_f64() * 1000.0));
+    }
+}
+
+/// Collects plotting closures to be run after everything else in a plot was plotted, see
+/// [`Plot::build_with_layers`].
+pub struct PlotLayers<'a> {
+    /// Closures to run at the end of the plot, in the order they were added
+    front: Vec<Box<dyn FnOnce() + 'a>>,
+}
+
+impl<'a> PlotLayers<'a> {
+    /// Defer the plotting done in `f` to after the closure passed to
+    /// [`Plot::build_with_layers`] has run, so it is drawn on top of the other elements. Closures
+    /// brought to the front are run in the order they were added, so later ones end up on top.
+    pub fn bring_to_front<F: FnOnce() + 'a>(&mut self, f: F) {
+        self.front.push(Box::new(f));
+    }
 }
 
 /// Tracks a plot that must be ended by calling `.end()`
-pub struct PlotToken {
+pub struct PlotToken<'a> {
     context: *const Context,
     /// For better error messages
     plot_title: CString,
+    /// Style variables pushed for this plot only, popped after the plot has ended
+    style_var_tokens: Vec<StyleVarToken>,
+    /// Style colors pushed for this plot only, popped after the plot has ended
+    style_color_tokens: Vec<StyleColorToken>,
+    /// Rotated X tick labels to draw before the plot ends, as tuples of axis, angle and
+    /// `(position, label)` pairs
+    rotated_tick_labels: Vec<RotatedTickLabels>,
+    /// Whether the extents of the plotted data are tracked for this plot
+    tracks_extents: bool,
+    /// Whether the limits of the first X axis are stored for X axes mirroring it
+    stores_x_limits: bool,
+    /// Whether the limits and plot area size are stored for keeping the aspect ratio
+    stores_view: bool,
+    /// Callback data of the custom axis scales, which ImPlot uses until the plot has ended. ImPlot
+    /// keeps pointers to it, so each one is boxed to keep it in place.
+    #[allow(clippy::vec_box)]
+    axis_transforms: Vec<Box<AxisTransformData>>,
+    /// Callback data of the custom tick label formats, which ImPlot uses until the plot has
+    /// ended. ImPlot keeps pointers to it, so each one is boxed to keep it in place.
+    #[allow(clippy::vec_box)]
+    axis_formatters: Vec<Box<AxisFormatterData>>,
+    /// Text to show in the center of the plot if nothing was plotted into it
+    empty_text: Option<CString>,
+    /// Axes and scales of the plot, for capturing its viewport
+    viewport_template: Viewport,
+    /// Axes whose limits are animated, and have to be stored for the next frame
+    animated_axes: Vec<Axis>,
+    /// Corners of the plot frame, if the plot is dimmed because imgui is in a disabled block
+    disabled_frame: Option<(ImVec2, ImVec2)>,
+    /// Ranges borrowed by stateful limits of the plot, which ImPlot writes to when it ends
+    stateful_limits: PhantomData<&'a mut ImPlotRange>,
 }
 
-impl PlotToken {
+impl PlotToken<'_> {
+    /// Get the items plotted into this plot so far, as their legend labels and the colors ImPlot
+    /// chose for them, in the order they were first plotted. Call this after plotting everything
+    /// and before [`PlotToken::end`], for example to draw a legend somewhere else while the plot
+    /// itself uses `PlotFlags::NO_LEGEND`. Items whose labels start with `##` are not shown in the
+    /// legend and are left out here as well, and the hidden `##` part of other labels is removed.
+    pub fn items(&self) -> Vec<(String, ImVec4)> {
+        crate::items::recorded_items()
+            .into_iter()
+            .filter_map(|(label, color)| {
+                let label = label.to_string_lossy();
+                if label.starts_with("##") {
+                    return None;
+                }
+                let visible = label.split("##").next().unwrap_or("");
+                Some((visible.to_string(), color))
+            })
+            .collect()
+    }
+
+    /// Whether this plot is the one the user interacted with most recently, by clicking or
+    /// scrolling in its plot area, out of all plots drawn through this library. This stays set
+    /// until the user interacts with another plot, so it can be used to show controls for the
+    /// plot last worked with, for example a toolbar next to it. Plots are told apart by their
+    /// titles.
+    pub fn is_active(&self) -> bool {
+        crate::plot_state::is_active_plot(&self.plot_title)
+    }
+
+    /// Get the extents of the data plotted into this plot so far, or `None` if nothing with
+    /// finite coordinates was plotted yet or the plot does not track its extents (see
+    /// [`Plot::with_data_extents`]). Call this after plotting everything and before
+    /// [`PlotToken::end`] to get the union of all plotted items.
+    ///
+    /// Only the data plotted against the first X and Y axes is covered. Which axes data is
+    /// plotted against is followed through [`crate::set_axis`] and [`crate::plot_on_axis`], so
+    /// select axes with those rather than with the raw ImPlot functions.
+    pub fn data_extents(&self) -> Option<DataExtents> {
+        crate::extents::current_extents()?.first_axes()
+    }
+
+    /// Get descriptions of the items plotted into this plot so far, in the order they were first
+    /// plotted, with their full labels, plot element types, point counts, colors and, if the plot
+    /// was set up with [`Plot::with_item_info`], the bounds of their data. Unlike
+    /// [`PlotToken::items`], this includes items whose labels start with `##`. Call this after
+    /// plotting everything and before [`PlotToken::end`].
+    pub fn item_info(&self) -> Vec<ItemInfo> {
+        crate::items::recorded_item_info()
+    }
+
+    /// Capture the current zoom and pan state of this plot, as the limits and scales of all axes
+    /// it uses, to restore it later with [`Plot::with_viewport`]. Axes with a custom scale set
+    /// with [`Plot::with_axis_transform`] are reported with a linear scale.
+    pub fn capture_viewport(&self) -> Viewport {
+        let mut viewport = self.viewport_template;
+        for (k, limits) in viewport.x_limits.iter_mut().enumerate() {
+            if let Some(limits) = limits {
+                let range = get_plot_limits(get_x_axis_from_index(k).unwrap(), Axis::Y1).X;
+                *limits = [range.Min, range.Max];
+            }
+        }
+        for (k, limits) in viewport.y_limits.iter_mut().enumerate() {
+            if let Some(limits) = limits {
+                let range = get_plot_limits(Axis::X1, get_y_axis_from_index(k).unwrap()).Y;
+                *limits = [range.Min, range.Max];
+            }
+        }
+        viewport
+    }
+
     /// End a previously begin()'ed plot.
     #[rustversion::attr(since(1.48), doc(alias = "EndPlot"))]
     pub fn end(mut self) {
         self.context = std::ptr::null();
+        for (axis, angle, ticks) in &self.rotated_tick_labels {
+            draw_rotated_x_tick_labels(*axis, *angle, ticks);
+        }
+        if let Some(text) = &self.empty_text {
+            if crate::items::recorded_items().is_empty() {
+                let limits = get_plot_limits(Axis::X1, Axis::Y1);
+                unsafe {
+                    crate::set_first_axes();
+                    sys::ImPlot_PlotText(
+                        text.as_ptr(),
+                        (limits.X.Min + limits.X.Max) / 2.0,
+                        (limits.Y.Min + limits.Y.Max) / 2.0,
+                        ImVec2 { x: 0.0, y: 0.0 },
+                        0,
+                    );
+                }
+            }
+        }
+        let hidden_legend_entries = crate::items::hidden_legend_entries();
+        if hidden_legend_entries > 0 {
+            draw_legend_overflow_entry(hidden_legend_entries);
+        }
+        if self.tracks_extents {
+            crate::extents::end_tracking(&self.plot_title);
+        }
+        if self.stores_x_limits {
+            let limits = get_plot_limits(Axis::X1, Axis::Y1);
+            crate::plot_state::store_x_limits(&self.plot_title, limits.X);
+        }
+        if !self.animated_axes.is_empty() {
+            let limits = self
+                .animated_axes
+                .iter()
+                .map(|axis| {
+                    let range = if get_x_axis_index(*axis).is_some() {
+                        get_plot_limits(*axis, Axis::Y1).X
+                    } else {
+                        get_plot_limits(Axis::X1, *axis).Y
+                    };
+                    (*axis, range)
+                })
+                .collect();
+            crate::plot_state::store_axis_limits(&self.plot_title, limits);
+        }
+        if self.stores_view {
+            let limits = get_plot_limits(Axis::X1, Axis::Y1);
+            crate::plot_state::store_view(&self.plot_title, limits, get_plot_size());
+        }
         unsafe { sys::ImPlot_EndPlot() };
+        crate::take_pushed(crate::Stack::Plots);
+        if let Some((frame_min, frame_max)) = self.disabled_frame {
+            draw_disabled_overlay(frame_min, frame_max);
+        }
+        std::mem::take(&mut self.style_var_tokens)
+            .into_iter()
+            .rev()
+            .for_each(StyleVarToken::pop);
+        std::mem::take(&mut self.style_color_tokens)
+            .into_iter()
+            .rev()
+            .for_each(StyleColorToken::pop);
+        std::mem::take(&mut self.axis_transforms)
+            .iter_mut()
+            .for_each(|data| data.resume_panic());
+        std::mem::take(&mut self.axis_formatters)
+            .iter_mut()
+            .for_each(|data| data.resume_panic());
     }
 }
 
-impl Drop for PlotToken {
+impl Drop for PlotToken<'_> {
     fn drop(&mut self) {
         if !self.context.is_null() && !std::thread::panicking() {
             panic!(
@@ -846,3 +2673,80 @@ impl Drop for PlotToken {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncated_labels() {
+        // One pixel per byte
+        let width = |text: &str| text.len() as f32;
+        assert_eq!(truncate_label("Short", 10.0, width), None);
+        assert_eq!(
+            truncate_label("Quarterly revenue", 10.0, width),
+            Some("Quarter...".to_string())
+        );
+        assert_eq!(
+            truncate_label("Very long", 8.0, width),
+            Some("Very...".to_string())
+        );
+        assert_eq!(
+            truncate_label("Gr\u{fc}\u{df}e aus", 7.0, width),
+            Some("Gr\u{fc}...".to_string())
+        );
+        assert_eq!(
+            truncate_label("Anything", 1.0, width),
+            Some("...".to_string())
+        );
+    }
+
+    #[test]
+    fn y_range_fit() {
+        let plot = Plot::new("Range fit").with_y_range_fit(Axis::Y2);
+        assert!(plot.y_enabled[1]);
+        assert!(plot.y_flags[1].contains(AxisFlags::AUTO_FIT | AxisFlags::RANGE_FIT));
+        assert!(!plot.y_flags[0].intersects(AxisFlags::AUTO_FIT | AxisFlags::RANGE_FIT));
+        assert!(!plot.y_enabled[2]);
+    }
+
+    #[test]
+    fn si_prefixed_labels() {
+        assert_eq!(format_si_prefixed(0.0, "V"), "0V");
+        assert_eq!(format_si_prefixed(-0.0, ""), "0");
+        assert_eq!(format_si_prefixed(1000.0, ""), "1k");
+        assert_eq!(format_si_prefixed(1.5e6, "Hz"), "1.5MHz");
+        assert_eq!(format_si_prefixed(-2.0e9, "B"), "-2GB");
+        assert_eq!(format_si_prefixed(0.02, "s"), "20ms");
+        assert_eq!(format_si_prefixed(3.3e-6, "F"), "3.3\u{b5}F");
+        assert_eq!(format_si_prefixed(4.0e-9, "s"), "4ns");
+        assert_eq!(format_si_prefixed(999.9999, ""), "1k");
+        assert_eq!(format_si_prefixed(12.0, "m"), "12m");
+    }
+
+    #[test]
+    fn auto_formatted_values() {
+        let format = |value| {
+            let mut buf = [0u8; 32];
+            let length = auto_format(value, &mut buf);
+            assert_eq!(buf[length], 0);
+            String::from_utf8(buf[..length].to_vec()).unwrap()
+        };
+        assert_eq!(format(0.0), "0");
+        assert_eq!(format(-0.0), "0");
+        assert_eq!(format(42.0), "42");
+        assert_eq!(format(0.125), "0.125");
+        assert_eq!(format(1.23456), "1.235");
+        assert_eq!(format(12345.678), "12346");
+        assert_eq!(format(0.0012346), "0.001235");
+        assert_eq!(format(1.5e6), "1.5e6");
+        assert_eq!(format(-2.5e-7), "-2.5e-7");
+        assert_eq!(format(100000.0), "1e5");
+        assert_eq!(format(f64::NAN), "NaN");
+
+        let mut short = [0xffu8; 4];
+        assert_eq!(auto_format(0.125, &mut short), 3);
+        assert_eq!(&short, b"0.1\0");
+        assert_eq!(auto_format(1.0, &mut []), 0);
+    }
+}
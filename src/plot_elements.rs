@@ -3,15 +3,34 @@
 //! This module defines the various structs that can be used for drawing different things such
 //! as lines, bars, scatter plots and text in a plot. For the module to create plots themselves,
 //! see `plot`.
-use crate::sys;
+use crate::callback::{point_getter_trampoline, CallbackData};
+use crate::custom_elements::{
+    color_to_u32, draw_clipped, draw_dashed_polyline, legend_entry_color, to_pixels,
+};
+use crate::quantity::{quantity_values, Quantity};
+use crate::scratch::ScratchBuffer;
+use crate::{
+    drag_line_y, drag_point, push_colormap_from_preset, sys, Axis, Colormap, ImPlotRange,
+    ImPlotRect, ImVec2, ImVec4, Marker, IMPLOT_AUTO,
+};
+use std::convert::TryFrom;
 use std::ffi::CString;
-use std::os::raw::c_char;
+use std::os::raw::{c_char, c_int};
 
 use crate::plot::{
     BarsFlags,
+    DigitalFlags,
+    DragToolFlags,
+    DummyFlags,
+    ErrorBarsFlags,
     HeatmapFlags,
+    HistogramFlags,
+    ImageFlags,
+    InfLinesFlags,
     LineFlags,
     ScatterFlags,
+    ShadedFlags,
+    StatLines,
     StemsFlags,
     StairsFlags,
     TextFlags
@@ -19,12 +38,284 @@ use crate::plot::{
 
 pub use crate::sys::ImPlotPoint;
 
+// --- Data types --------------------------------------------------------------------------------
+mod sealed {
+    /// Keeps [`super::ImPlotData`] from being implemented outside of this crate, since each
+    /// implementation needs the matching ImPlot functions.
+    pub trait Sealed {}
+}
+
+// Signatures of the ImPlot functions for plotting data of one type
+type PlotLineFn<T> = unsafe extern "C" fn(
+    *const c_char,
+    *const T,
+    *const T,
+    c_int,
+    sys::ImPlotLineFlags,
+    c_int,
+    c_int,
+);
+type PlotScatterFn<T> = unsafe extern "C" fn(
+    *const c_char,
+    *const T,
+    *const T,
+    c_int,
+    sys::ImPlotScatterFlags,
+    c_int,
+    c_int,
+);
+type PlotBarsFn<T> = unsafe extern "C" fn(
+    *const c_char,
+    *const T,
+    *const T,
+    c_int,
+    f64,
+    sys::ImPlotBarsFlags,
+    c_int,
+    c_int,
+);
+type PlotStemsFn<T> = unsafe extern "C" fn(
+    *const c_char,
+    *const T,
+    *const T,
+    c_int,
+    f64,
+    sys::ImPlotStemsFlags,
+    c_int,
+    c_int,
+);
+
+/// Numeric types that [`PlotLine`], [`PlotScatter`], [`PlotBars`] and [`PlotStems`] can plot
+/// directly, without converting the data to `f64` first. ImPlot has separate functions for each
+/// type, and this trait picks the right one. It is implemented for `f64`, `f32`, `i64`, `i32`,
+/// `u32`, `u16` and `u8`, and can not be implemented outside of this crate.
+pub trait ImPlotData: Copy + sealed::Sealed {
+    #[doc(hidden)]
+    const PLOT_LINE: PlotLineFn<Self>;
+    #[doc(hidden)]
+    const PLOT_SCATTER: PlotScatterFn<Self>;
+    #[doc(hidden)]
+    const PLOT_BARS: PlotBarsFn<Self>;
+    #[doc(hidden)]
+    const PLOT_STEMS: PlotStemsFn<Self>;
+
+    /// The value as an `f64`, which is how this crate looks at plotted data itself, for example
+    /// for tracking extents. This is exact except for 64 bit integers beyond 2^53.
+    fn to_f64(self) -> f64;
+
+    /// The values as `f64` without copying them, if they already are.
+    #[doc(hidden)]
+    fn as_f64_slice(_values: &[Self]) -> Option<&[f64]> {
+        None
+    }
+}
+
+macro_rules! impl_plot_data {
+    ($($data_type:ty => ($line:ident, $scatter:ident, $bars:ident, $stems:ident)),* $(,)?) => {
+        $(
+            impl sealed::Sealed for $data_type {}
+
+            impl ImPlotData for $data_type {
+                const PLOT_LINE: PlotLineFn<Self> = sys::$line;
+                const PLOT_SCATTER: PlotScatterFn<Self> = sys::$scatter;
+                const PLOT_BARS: PlotBarsFn<Self> = sys::$bars;
+                const PLOT_STEMS: PlotStemsFn<Self> = sys::$stems;
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_plot_data! {
+    f32 => (
+        ImPlot_PlotLine_FloatPtrFloatPtr,
+        ImPlot_PlotScatter_FloatPtrFloatPtr,
+        ImPlot_PlotBars_FloatPtrFloatPtr,
+        ImPlot_PlotStems_FloatPtrFloatPtr
+    ),
+    i64 => (
+        ImPlot_PlotLine_S64PtrS64Ptr,
+        ImPlot_PlotScatter_S64PtrS64Ptr,
+        ImPlot_PlotBars_S64PtrS64Ptr,
+        ImPlot_PlotStems_S64PtrS64Ptr
+    ),
+    i32 => (
+        ImPlot_PlotLine_S32PtrS32Ptr,
+        ImPlot_PlotScatter_S32PtrS32Ptr,
+        ImPlot_PlotBars_S32PtrS32Ptr,
+        ImPlot_PlotStems_S32PtrS32Ptr
+    ),
+    u32 => (
+        ImPlot_PlotLine_U32PtrU32Ptr,
+        ImPlot_PlotScatter_U32PtrU32Ptr,
+        ImPlot_PlotBars_U32PtrU32Ptr,
+        ImPlot_PlotStems_U32PtrU32Ptr
+    ),
+    u16 => (
+        ImPlot_PlotLine_U16PtrU16Ptr,
+        ImPlot_PlotScatter_U16PtrU16Ptr,
+        ImPlot_PlotBars_U16PtrU16Ptr,
+        ImPlot_PlotStems_U16PtrU16Ptr
+    ),
+    u8 => (
+        ImPlot_PlotLine_U8PtrU8Ptr,
+        ImPlot_PlotScatter_U8PtrU8Ptr,
+        ImPlot_PlotBars_U8PtrU8Ptr,
+        ImPlot_PlotStems_U8PtrU8Ptr
+    ),
+}
+
+// f64 is implemented by hand, since it is the one type that needs no conversion
+impl sealed::Sealed for f64 {}
+
+impl ImPlotData for f64 {
+    const PLOT_LINE: PlotLineFn<Self> = sys::ImPlot_PlotLine_doublePtrdoublePtr;
+    const PLOT_SCATTER: PlotScatterFn<Self> = sys::ImPlot_PlotScatter_doublePtrdoublePtr;
+    const PLOT_BARS: PlotBarsFn<Self> = sys::ImPlot_PlotBars_doublePtrdoublePtr;
+    const PLOT_STEMS: PlotStemsFn<Self> = sys::ImPlot_PlotStems_doublePtrdoublePtr;
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn as_f64_slice(values: &[Self]) -> Option<&[f64]> {
+        Some(values)
+    }
+}
+
+/// Internal helper to convert plotted data to `f64`, in a buffer borrowed from the scratch pool.
+fn f64_values<T: ImPlotData>(values: &[T]) -> ScratchBuffer {
+    values.iter().map(|value| value.to_f64()).collect()
+}
+
+/// Internal helper to report plotted data of any type to the extents and recordings, converting
+/// it to `f64` only if the points are looked at and are not `f64` already.
+fn track_data_points<T: ImPlotData>(x: &[T], y: &[T]) {
+    if let (Some(x), Some(y)) = (T::as_f64_slice(x), T::as_f64_slice(y)) {
+        crate::extents::track_points(x, y);
+    } else if crate::extents::wants_points() {
+        crate::extents::track_points(&f64_values(x), &f64_values(y));
+    }
+}
+
+/// How the values of plotted data are laid out in memory, see [`PlotLine::with_stride`] and
+/// [`PlotLine::with_offset`]. By default, the values follow each other directly and the first
+/// one is plotted first.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+struct DataLayout {
+    /// Distance between consecutive values in bytes, if it is not the size of one value
+    stride: Option<i32>,
+    /// Index of the value that is plotted first, counted in values
+    offset: i32,
+}
+
+impl DataLayout {
+    /// The stride in values, checked to be valid for values of type `T`.
+    fn step<T>(&self) -> usize {
+        let size = std::mem::size_of::<T>();
+        match self.stride {
+            None => 1,
+            Some(stride) => usize::try_from(stride)
+                .ok()
+                .filter(|bytes| *bytes > 0 && bytes.is_multiple_of(size))
+                .map(|bytes| bytes / size)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Stride of {} bytes is not a positive multiple of the value size of {} bytes",
+                        stride, size
+                    )
+                }),
+        }
+    }
+
+    /// The stride in bytes, as ImPlot takes it.
+    fn stride_bytes<T>(&self) -> i32 {
+        // This is either the stride that was set or the size of a number, so it fits
+        (self.step::<T>() * std::mem::size_of::<T>()) as i32
+    }
+
+    /// Number of points that can be read from both `x` and `y` with this layout.
+    fn point_count<T>(&self, x: &[T], y: &[T]) -> usize {
+        let step = self.step::<T>();
+        let points_in = |values: &[T]| values.len().div_ceil(step);
+        points_in(x).min(points_in(y))
+    }
+
+    /// Read `count` values as `f64`, in the order ImPlot plots them in. Values before the
+    /// offset come after the last one, and negative offsets count from the end.
+    fn values<T: ImPlotData>(&self, values: &[T], count: usize) -> ScratchBuffer {
+        let step = self.step::<T>();
+        let first = (self.offset as i64).rem_euclid(count.max(1) as i64) as usize;
+        (0..count)
+            .map(|i| values[(first + i) % count * step].to_f64())
+            .collect()
+    }
+
+    /// Report `count` points of `x` and `y` to the extents and recordings.
+    fn track_points<T: ImPlotData>(&self, x: &[T], y: &[T], count: usize) {
+        if *self == DataLayout::default() {
+            track_data_points(&x[..count], &y[..count]);
+        } else if crate::extents::wants_points() {
+            crate::extents::track_points(&self.values(x, count), &self.values(y, count));
+        }
+    }
+}
+
 // --- Actual plotting functionality -------------------------------------------------------------
+/// What to do with values that are zero or negative, which have no position on a logarithmic
+/// axis, see [`PlotLine::plot_log_safe`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum NonPositiveValues {
+    /// Leave a gap in the line where the values are
+    Gap,
+    /// Leave the points out, connecting the points around them directly
+    Skip,
+    /// Replace the values with the given positive value, e.g. a floor just below the data
+    Clamp(f64),
+}
+
+/// Dash pattern of a line, see [`PlotLine::with_line_style`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LineStyle {
+    /// A continuous line, drawn by ImPlot itself
+    Solid,
+    /// Dashes about six times as long as the line is thick
+    Dashed,
+    /// Dots the size of the line thickness
+    Dotted,
+    /// Alternating dashes and dots
+    DashDot,
+}
+
+impl LineStyle {
+    /// Lengths of the alternating drawn and skipped parts of the pattern, in multiples of the
+    /// line weight. Empty for solid lines.
+    fn dash_pattern(self) -> &'static [f32] {
+        match self {
+            LineStyle::Solid => &[],
+            LineStyle::Dashed => &[6.0, 4.0],
+            LineStyle::Dotted => &[1.0, 2.0],
+            LineStyle::DashDot => &[6.0, 3.0, 1.0, 3.0],
+        }
+    }
+}
+
 /// Struct to provide functionality for plotting a line in a plot.
 pub struct PlotLine {
     /// Label to show in the legend for this line
     label: CString,
-    flags: LineFlags
+    flags: LineFlags,
+    /// How [`PlotLine::plot_log_safe`] handles values that are zero or negative
+    non_positive_values: NonPositiveValues,
+    /// Whether [`PlotLine::plot_log_safe`] handles X values as well as Y values
+    log_safe_x: bool,
+    /// Dash pattern the line is drawn with
+    line_style: LineStyle,
+    /// Stride and offset of the data passed to [`PlotLine::plot`]
+    layout: DataLayout,
 }
 
 impl PlotLine {
@@ -44,7 +335,11 @@ impl PlotLine {
         Self {
             label: CString::new(label)
                 .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
-            flags
+            flags,
+            non_positive_values: NonPositiveValues::Gap,
+            log_safe_x: false,
+            line_style: LineStyle::Solid,
+            layout: DataLayout::default(),
         }
     }
 
@@ -53,24 +348,660 @@ impl PlotLine {
         self
     }
 
+    /// Set the dash pattern of the line, for example to tell apart reference lines that overlap
+    /// with the data. ImPlot only draws solid lines, so lines with other styles are drawn onto
+    /// the plot's draw list instead, in the color ImPlot assigns to the item and with the line
+    /// weight of the current style (see [`crate::StyleVar::LineWeight`]). The `SEGMENTS`,
+    /// `LOOP` and `SKIP_NAN` flags are respected, other flags such as `SHADED` are not.
+    ///
+    /// Such lines still get a single legend entry, but ImPlot draws its legend icons itself, so
+    /// the icon is a plain swatch of the line color rather than the dash pattern. Like other
+    /// custom elements such as [`crate::PlotQuiver`], they are not hidden when the legend entry
+    /// is clicked, and the plot does not fit itself to them.
+    pub fn with_line_style(mut self, line_style: LineStyle) -> Self {
+        self.line_style = line_style;
+        self
+    }
+
+    /// Set the distance between consecutive values of the data in **bytes**, to plot values that
+    /// are interleaved with other data without copying them out. Defaults to the size of one
+    /// value, for values that directly follow each other. The slices passed to
+    /// [`PlotLine::plot`] then start at the first value and span the data in between, and as
+    /// many points are plotted as both of them hold. For example, for an array of structs:
+    /// ```no_run
+    /// # use implot::PlotLine;
+    /// #[repr(C)]
+    /// struct Point {
+    ///     x: f64,
+    ///     y: f64,
+    /// }
+    ///
+    /// # fn draw(points: &[Point]) {
+    /// // The fields of all points as one slice: x0, y0, x1, y1, ...
+    /// let fields = unsafe {
+    ///     std::slice::from_raw_parts(points.as_ptr() as *const f64, points.len() * 2)
+    /// };
+    /// PlotLine::new("Points")
+    ///     .with_stride(std::mem::size_of::<Point>() as i32)
+    ///     .plot(&fields[0..], &fields[1..]);
+    /// # }
+    /// ```
+    ///
+    /// The stride and offset only apply to [`PlotLine::plot`] and [`PlotLine::plot_f32`]. The
+    /// other plotting methods, and hit testing with [`Hoverable`], take plain data. For data
+    /// that is not in a slice, such as a mapped device buffer, see [`PlotLine::plot_raw`].
+    ///
+    /// # Panics
+    /// Plotting will panic if the stride is not a positive multiple of the size of one value.
+    pub fn with_stride(mut self, bytes: i32) -> Self {
+        self.layout.stride = Some(bytes);
+        self
+    }
+
+    /// Set the index of the value that is plotted first, counted in **values** rather than bytes.
+    /// The values before it are plotted after the last one, so a ring buffer is plotted in order
+    /// by passing the index of its oldest value. Negative offsets count from the end. Defaults
+    /// to 0.
+    pub fn with_offset(mut self, count: i32) -> Self {
+        self.layout.offset = count;
+        self
+    }
+
+    /// Set how [`PlotLine::plot_log_safe`] handles values that are zero or negative. Defaults to
+    /// leaving gaps in the line.
+    ///
+    /// # Panics
+    /// Will panic if a [`NonPositiveValues::Clamp`] floor is not positive, since the clamped
+    /// values could not be placed on a logarithmic axis either.
+    pub fn with_non_positive_values(mut self, non_positive_values: NonPositiveValues) -> Self {
+        if let NonPositiveValues::Clamp(floor) = non_positive_values {
+            assert!(floor > 0.0, "Clamp floor {} is not positive", floor);
+        }
+        self.non_positive_values = non_positive_values;
+        self
+    }
+
+    /// Set whether [`PlotLine::plot_log_safe`] handles X values that are zero or negative too,
+    /// for lines on a logarithmic X axis. Defaults to only handling Y values, since lines on a
+    /// logarithmic Y axis usually have a linear X axis on which any value can be placed.
+    pub fn with_log_safe_x(mut self, enabled: bool) -> Self {
+        self.log_safe_x = enabled;
+        self
+    }
+
     /// Plot a line. Use this in closures passed to [`Plot::build()`](struct.Plot.html#method.build)
-    pub fn plot(&self, x: &[f64], y: &[f64]) {
+    ///
+    /// The points are connected in the order they are given in, not sorted by X, so this can
+    /// draw parametric curves such as circles. For data that is not sorted by X but should be
+    /// drawn as a function of X, use [`PlotLine::plot_sorted`].
+    ///
+    /// The data can be of any type implementing [`ImPlotData`], such as `f64`, `f32` or `i32`.
+    pub fn plot<T: ImPlotData>(&self, x: &[T], y: &[T]) {
+        self.plot_with_layout(x, y, self.layout);
+    }
+
+    /// Internal helper to plot data prepared by this crate, which ignores the stride and offset
+    /// set on the line.
+    fn plot_plain<T: ImPlotData>(&self, x: &[T], y: &[T]) {
+        self.plot_with_layout(x, y, DataLayout::default());
+    }
+
+    /// Internal helper to plot a line from data with the given layout.
+    fn plot_with_layout<T: ImPlotData>(&self, x: &[T], y: &[T], layout: DataLayout) {
+        let number_of_points = layout.point_count(x, y);
         // If there is no data to plot, we stop here
-        if x.len().min(y.len()) == 0 {
+        if number_of_points == 0 {
+            return;
+        }
+        if self.line_style != LineStyle::Solid {
+            // Lines with dash patterns are drawn by this crate, from f64 data
+            let x = layout.values(x, number_of_points);
+            let y = layout.values(y, number_of_points);
+            crate::extents::track_points(&x, &y);
+            self.plot_dashed(&x, &y);
             return;
         }
+        layout.track_points(x, y, number_of_points);
         unsafe {
-            sys::ImPlot_PlotLine_doublePtrdoublePtr(
-                self.label.as_ptr() as *const c_char,
+            T::PLOT_LINE(
+                crate::items::legend_label(&self.label).as_ptr(),
                 x.as_ptr(),
                 y.as_ptr(),
-                x.len().min(y.len()) as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
-               self.flags.bits() as sys::ImPlotLineFlags_, 
-                0,                           // No offset
+                number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                self.flags.bits() as sys::ImPlotLineFlags_,
+                layout.offset,
+                layout.stride_bytes::<T>(),
+            );
+        }
+        crate::items::record_last_item(&self.label, "PlotLine", number_of_points);
+    }
+
+    /// Plot a line from `f32` data, such as telemetry buffers, without converting it to `f64`
+    /// first. This is the same as [`PlotLine::plot`] with `f32` data, and is kept for code that
+    /// used it before `plot` took other types than `f64`.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotLine"))]
+    pub fn plot_f32(&self, x: &[f32], y: &[f32]) {
+        self.plot(x, y);
+    }
+
+    /// Internal helper to draw a line with a dash pattern onto the plot's draw list, see
+    /// [`PlotLine::with_line_style`].
+    fn plot_dashed(&self, x: &[f64], y: &[f64]) {
+        let number_of_points = x.len().min(y.len());
+        let color = color_to_u32(legend_entry_color(
+            &self.label,
+            None,
+            "PlotLine",
+            number_of_points,
+        ));
+        let weight = unsafe { (*sys::ImPlot_GetStyle()).LineWeight }.max(1.0);
+        let pattern: Vec<f32> = self
+            .line_style
+            .dash_pattern()
+            .iter()
+            .map(|length| length * weight)
+            .collect();
+
+        let skip_nan = self.flags.contains(LineFlags::SKIP_NAN);
+        let mut points: Vec<ImVec2> = (0..number_of_points)
+            .filter(|&i| !skip_nan || !(x[i].is_nan() || y[i].is_nan()))
+            .map(|i| to_pixels(x[i], y[i]))
+            .collect();
+        let segments = self.flags.contains(LineFlags::SEGMENTS);
+        if self.flags.contains(LineFlags::LOOP) && !segments {
+            if let Some(&first) = points.first() {
+                points.push(first);
+            }
+        }
+
+        // Dashes outside of the plot area would be clipped anyway, so they are not generated
+        let (min, max) = crate::get_plot_area_rect();
+        let clip = (
+            ImVec2 {
+                x: min.x - weight,
+                y: min.y - weight,
+            },
+            ImVec2 {
+                x: max.x + weight,
+                y: max.y + weight,
+            },
+        );
+        draw_clipped(|draw_list| {
+            if segments {
+                for segment in points.chunks_exact(2) {
+                    draw_dashed_polyline(draw_list, segment, clip, &pattern, color, weight);
+                }
+            } else {
+                draw_dashed_polyline(draw_list, &points, clip, &pattern, color, weight);
+            }
+        });
+    }
+
+    /// Plot a line of quantities that carry their unit in their type, converted to values in
+    /// that unit. Use [`Plot::with_x_axis_quantity`](struct.Plot.html#method.with_x_axis_quantity)
+    /// and its Y counterpart with the same types to label the axes with the units. Use this in
+    /// closures passed to [`Plot::build()`](struct.Plot.html#method.build)
+    pub fn plot_quantities<X: Quantity, Y: Quantity>(&self, x: &[X], y: &[Y]) {
+        self.plot_plain(&quantity_values(x), &quantity_values(y));
+    }
+
+    /// Plot a line, and markers at only the points with the given indices, for example to mark
+    /// events on a dense time series. The markers are plotted as a scatter plot under the same
+    /// label, so they share the line's legend entry and color, and are hidden together with the
+    /// line from the legend. Indices past the end of the data are ignored. Use this in closures
+    /// passed to [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "PlotScatter"))]
+    pub fn plot_with_markers_at(
+        &self,
+        x: &[f64],
+        y: &[f64],
+        marker_indices: &[usize],
+        marker: Marker,
+    ) {
+        self.plot_plain(x, y);
+
+        let number_of_points = x.len().min(y.len());
+        let indices = marker_indices.iter().filter(|&&i| i < number_of_points);
+        let marker_x: ScratchBuffer = indices.clone().map(|&i| x[i]).collect();
+        let marker_y: ScratchBuffer = indices.map(|&i| y[i]).collect();
+        // If there are no markers to plot, we stop here
+        if marker_x.is_empty() {
+            return;
+        }
+        crate::extents::track_points(&marker_x, &marker_y);
+        let auto_color = ImVec4 { x: 0.0, y: 0.0, z: 0.0, w: -1.0 }; // IMPLOT_AUTO_COL
+        unsafe {
+            sys::ImPlot_SetNextMarkerStyle(
+                marker as sys::ImPlotMarker,
+                IMPLOT_AUTO as f32,
+                auto_color,
+                IMPLOT_AUTO as f32,
+                auto_color,
+            );
+            sys::ImPlot_PlotScatter_doublePtrdoublePtr(
+                crate::items::legend_label(&self.label).as_ptr(),
+                marker_x.as_ptr(),
+                marker_y.as_ptr(),
+                marker_x.len() as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                0,                     // No flags
+                0,                     // No offset
                 std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
             );
         }
+        crate::items::record_last_item(&self.label, "PlotLine", marker_x.len());
+    }
+
+    /// Plot a line through the points sorted by their X values, instead of connecting them in
+    /// the order they are given in. This avoids the zig-zag lines [`PlotLine::plot`] draws for
+    /// data that is not sorted by X. The data is copied for sorting, and points with equal X
+    /// values keep their order. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    pub fn plot_sorted(&self, x: &[f64], y: &[f64]) {
+        let number_of_points = x.len().min(y.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+
+        let mut indices: Vec<usize> = (0..number_of_points).collect();
+        indices.sort_by(|a, b| x[*a].partial_cmp(&x[*b]).unwrap_or(std::cmp::Ordering::Equal));
+        let sorted_x: ScratchBuffer = indices.iter().map(|i| x[*i]).collect();
+        let sorted_y: ScratchBuffer = indices.iter().map(|i| y[*i]).collect();
+        self.plot_plain(&sorted_x, &sorted_y);
+    }
+
+    /// Plot data made of several lines separated by NaN values, such as concatenated
+    /// trajectories, as separate items with one label per line, so each can be toggled in the
+    /// legend by itself. A point whose X or Y value is NaN ends a segment, and segments without
+    /// any points, for example between consecutive NaNs, are not counted. The flags of this line
+    /// apply to all segments, and its own label is not used. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    ///
+    /// # Panics
+    /// Will panic if the number of segments differs from the number of labels, or if a label
+    /// contains internal null bytes.
+    pub fn plot_segments(&self, x: &[f64], y: &[f64], labels: &[&str]) {
+        let number_of_points = x.len().min(y.len());
+        let segments = nan_separated_segments(&x[..number_of_points], &y[..number_of_points]);
+        assert_eq!(
+            segments.len(),
+            labels.len(),
+            "The data has {} NaN-separated segments, but {} labels were given",
+            segments.len(),
+            labels.len()
+        );
+
+        for (segment, label) in segments.into_iter().zip(labels) {
+            PlotLine::new_with_flags(label, self.flags).plot(&x[segment.clone()], &y[segment]);
+        }
+    }
+
+    /// Plot `y` against X positions spaced evenly on a logarithmic scale from `x_start` to
+    /// `x_stop`, one per value, as for a frequency sweep. This is meant for an X axis with a
+    /// logarithmic scale, set with `Plot::with_axis_scale(Axis::X1, AxisScale::Log10)`, on which
+    /// the points end up evenly spaced; on a linear axis, they bunch up towards `x_start`.
+    /// `x_stop` may be smaller than `x_start` for descending positions, and a single value is
+    /// placed at `x_start`. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    ///
+    /// # Panics
+    /// Will panic if `x_start` or `x_stop` is not positive and finite, since such values have no
+    /// place on a logarithmic scale.
+    pub fn plot_logspace(&self, y: &[f64], x_start: f64, x_stop: f64) {
+        assert!(
+            x_start > 0.0 && x_start.is_finite() && x_stop > 0.0 && x_stop.is_finite(),
+            "Log-spaced X positions need a positive and finite start and stop, got {} and {}",
+            x_start,
+            x_stop
+        );
+        // If there is no data to plot, we stop here
+        if y.is_empty() {
+            return;
+        }
+
+        let (log_start, log_stop) = (x_start.log10(), x_stop.log10());
+        let step = if y.len() > 1 {
+            (log_stop - log_start) / (y.len() - 1) as f64
+        } else {
+            0.0
+        };
+        let x: ScratchBuffer = (0..y.len())
+            .map(|i| 10f64.powf(log_start + i as f64 * step))
+            .collect();
+        self.plot_plain(&x, y);
+    }
+
+    /// Plot a line on a logarithmic Y axis, handling Y values that are zero or negative as set with
+    /// [`PlotLine::with_non_positive_values`], and X values as well if set with
+    /// [`PlotLine::with_log_safe_x`]. [`PlotLine::plot`] passes such values on to ImPlot
+    /// unchanged, which cannot place them on a logarithmic axis, so the line either misses
+    /// segments or the plot looks empty without any indication why. NaN values are treated like
+    /// non-positive ones. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    pub fn plot_log_safe(&self, x: &[f64], y: &[f64]) {
+        let number_of_points = x.len().min(y.len());
+        let (x, y) = (&x[..number_of_points], &y[..number_of_points]);
+        let is_valid = |value: f64| value > 0.0;
+        let is_valid_x = |value: f64| !self.log_safe_x || is_valid(value);
+        match self.non_positive_values {
+            NonPositiveValues::Gap => {
+                // NaN values are shown as missing data by ImPlot unless SKIP_NAN is set
+                let y: ScratchBuffer = x
+                    .iter()
+                    .zip(y.iter())
+                    .map(|(x, y)| {
+                        if is_valid_x(*x) && is_valid(*y) {
+                            *y
+                        } else {
+                            f64::NAN
+                        }
+                    })
+                    .collect();
+                Self {
+                    label: self.label.clone(),
+                    flags: self.flags - LineFlags::SKIP_NAN,
+                    non_positive_values: self.non_positive_values,
+                    log_safe_x: self.log_safe_x,
+                    line_style: self.line_style,
+                    layout: DataLayout::default(),
+                }
+                .plot(x, &y);
+            }
+            NonPositiveValues::Skip => {
+                let (mut kept_x, mut kept_y) = (ScratchBuffer::new(), ScratchBuffer::new());
+                for (x, y) in x.iter().zip(y.iter()) {
+                    if is_valid_x(*x) && is_valid(*y) {
+                        kept_x.push(*x);
+                        kept_y.push(*y);
+                    }
+                }
+                self.plot_plain(&kept_x, &kept_y);
+            }
+            NonPositiveValues::Clamp(floor) => {
+                let clamp = |value: &f64| if is_valid(*value) { *value } else { floor };
+                let y: ScratchBuffer = y.iter().map(clamp).collect();
+                if self.log_safe_x {
+                    let x: ScratchBuffer = x.iter().map(clamp).collect();
+                    self.plot_plain(&x, &y);
+                } else {
+                    self.plot_plain(x, &y);
+                }
+            }
+        }
+    }
+
+    /// Plot a smoothed version of the line, computed as a centered moving average over `window`
+    /// points. Close to the ends of the data, the window shrinks to the points that are
+    /// available. The smoothed line is labeled like this line with " (smoothed)" appended, and
+    /// uses the same flags. A window of zero or one point plots the data unchanged. Use this in
+    /// closures passed to [`Plot::build()`](struct.Plot.html#method.build), typically after
+    /// plotting the raw data with [`PlotLine::plot`].
+    pub fn plot_smoothed(&self, x: &[f64], y: &[f64], window: usize) {
+        let number_of_points = x.len().min(y.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+
+        let smoothed = centered_moving_average(&y[..number_of_points], window);
+        Self {
+            label: with_label_suffix(&self.label, " (smoothed)"),
+            flags: self.flags,
+            non_positive_values: self.non_positive_values,
+            log_safe_x: self.log_safe_x,
+            line_style: self.line_style,
+            layout: DataLayout::default(),
+        }
+        .plot(&x[..number_of_points], &smoothed);
+    }
+
+    /// Plot the line together with lines or bands for statistics of its Y values, selected with
+    /// `stats`: horizontal lines at the mean or median, and a shaded band of one standard
+    /// deviation around the mean. These are drawn in the color of the line, and labeled like the
+    /// line with " (mean)", " (median)" or " (std)" appended, so they get their own legend
+    /// entries. Non-finite values are left out of the statistics. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build).
+    pub fn plot_with_stat_lines(&self, x: &[f64], y: &[f64], stats: StatLines) {
+        let number_of_points = x.len().min(y.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+
+        self.plot_plain(&x[..number_of_points], &y[..number_of_points]);
+        let mut color = ImVec4 { x: 0.0, y: 0.0, z: 0.0, w: 0.0 };
+        unsafe { sys::ImPlot_GetLastItemColor(&mut color as *mut ImVec4) };
+
+        let mut values: ScratchBuffer = y[..number_of_points]
+            .iter()
+            .copied()
+            .filter(|value| value.is_finite())
+            .collect();
+        if values.is_empty() {
+            return;
+        }
+        let count = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / count;
+
+        if stats.contains(StatLines::STD_BANDS) {
+            let std = (values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / count).sqrt();
+            let finite_x = x[..number_of_points].iter().filter(|value| value.is_finite());
+            let x_min = finite_x.clone().fold(f64::INFINITY, |a, b| a.min(*b));
+            let x_max = finite_x.fold(f64::NEG_INFINITY, |a, b| a.max(*b));
+            let label = with_label_suffix(&self.label, " (std)");
+            unsafe {
+                sys::ImPlot_SetNextFillStyle(color, 0.25);
+                sys::ImPlot_PlotShaded_doublePtrdoublePtrdoublePtr(
+                    crate::items::legend_label(&label).as_ptr(),
+                    [x_min, x_max].as_ptr(),
+                    [mean - std; 2].as_ptr(),
+                    [mean + std; 2].as_ptr(),
+                    2,
+                    0,                                 // No flags
+                    0,                                 // No offset
+                    std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+                );
+            }
+            crate::items::record_last_item(&label, "PlotLine", 2);
+        }
+        if stats.contains(StatLines::MEAN) {
+            plot_stat_line(&with_label_suffix(&self.label, " (mean)"), mean, color);
+        }
+        if stats.contains(StatLines::MEDIAN) {
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let middle = values.len() / 2;
+            let median = if values.len().is_multiple_of(2) {
+                (values[middle - 1] + values[middle]) / 2.0
+            } else {
+                values[middle]
+            };
+            plot_stat_line(&with_label_suffix(&self.label, " (median)"), median, color);
+        }
+    }
+
+    /// Plot a line directly from raw pointers, without going through slices, for example from a
+    /// memory-mapped file. `count` points are read from each pointer, and `stride` is the
+    /// distance in bytes between consecutive values, which is `size_of::<f64>()` for tightly
+    /// packed data. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    ///
+    /// # Safety
+    /// For the duration of this call, `x_ptr` and `y_ptr` must each point to `count` readable,
+    /// properly aligned and initialized `f64` values spaced `stride` bytes apart, and that memory
+    /// must not be written to concurrently, for example by another thread or a device. ImPlot
+    /// does not keep the pointers after this call returns.
+    ///
+    /// # Panics
+    /// Will panic if `stride` is not a multiple of the size of an `f64`, or does not fit in an
+    /// `i32`.
+    pub unsafe fn plot_raw(&self, x_ptr: *const f64, y_ptr: *const f64, count: usize, stride: usize) {
+        // If there is no data to plot, we stop here
+        if count == 0 {
+            return;
+        }
+        assert!(
+            stride.is_multiple_of(std::mem::size_of::<f64>()),
+            "Stride {} is not a multiple of the size of an f64",
+            stride
+        );
+        let stride_i32 = i32::try_from(stride).expect("Stride does not fit in an i32");
+
+        if crate::extents::wants_points() {
+            // Only copy the data when someone is interested in its extents
+            let step = stride / std::mem::size_of::<f64>();
+            let x: ScratchBuffer = (0..count).map(|i| *x_ptr.add(i * step)).collect();
+            let y: ScratchBuffer = (0..count).map(|i| *y_ptr.add(i * step)).collect();
+            crate::extents::track_points(&x, &y);
+        }
+        sys::ImPlot_PlotLine_doublePtrdoublePtr(
+            crate::items::legend_label(&self.label).as_ptr(),
+            x_ptr,
+            y_ptr,
+            count as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+            self.flags.bits() as sys::ImPlotLineFlags_,
+            0, // No offset
+            stride_i32,
+        );
+        crate::items::record_last_item(&self.label, "PlotLine", count);
+    }
+
+    /// Plot a line of `count` points returned by `getter` for their indices, from 0 to
+    /// `count - 1`. The points are requested while ImPlot draws the line, so large or computed
+    /// series, such as a live signal of millions of samples in a ring buffer, can be plotted
+    /// without copying them into slices first. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    ///
+    /// ImPlot may ask for the same point more than once, for example when fitting the axes to
+    /// the data, so the getter should be cheap and return the same point for the same index.
+    /// When the extents of the plot are tracked or plot calls are recorded, the getter is also
+    /// called for every point once before the line is plotted, and the points are looked at in
+    /// chunks rather than all at once. The stride, offset and line style of the line are not
+    /// used.
+    ///
+    /// # Panics
+    /// A panic in the getter does not unwind through ImPlot. The remaining points are left out
+    /// of the line, and the panic is resumed once ImPlot has returned.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotLineG"))]
+    pub fn plot_with_getter<F: FnMut(i32) -> ImPlotPoint>(&self, count: i32, mut getter: F) {
+        // If there is no data to plot, we stop here
+        if count <= 0 {
+            return;
+        }
+        if crate::extents::wants_points() {
+            const CHUNK_SIZE: i32 = 4096;
+            let (mut x, mut y) = (ScratchBuffer::new(), ScratchBuffer::new());
+            for start in (0..count).step_by(CHUNK_SIZE as usize) {
+                x.clear();
+                y.clear();
+                for idx in start..count.min(start.saturating_add(CHUNK_SIZE)) {
+                    let point = getter(idx);
+                    x.push(point.X);
+                    y.push(point.Y);
+                }
+                crate::extents::track_points(&x, &y);
+            }
+        }
+
+        // The getter lives on the stack for the duration of the call, ImPlot does not keep it
+        let mut data = CallbackData::new(getter);
+        unsafe {
+            sys::ImPlot_PlotLineG(
+                crate::items::legend_label(&self.label).as_ptr(),
+                Some(point_getter_trampoline::<F>),
+                data.as_user_data(),
+                count,
+                self.flags.bits() as sys::ImPlotLineFlags_,
+            );
+        }
+        crate::items::record_last_item(&self.label, "PlotLine", count as usize);
+        data.resume_panic();
+    }
+}
+
+/// Draw an infinite horizontal line at the given value, for the statistics drawn by
+/// [`PlotLine::plot_with_stat_lines`].
+fn plot_stat_line(label: &CString, value: f64, color: ImVec4) {
+    unsafe {
+        sys::ImPlot_SetNextLineStyle(color, IMPLOT_AUTO as f32);
+        sys::ImPlot_PlotInfLines_doublePtr(
+            crate::items::legend_label(label).as_ptr(),
+            &value,
+            1,
+            sys::ImPlotInfLinesFlags__ImPlotInfLinesFlags_Horizontal,
+            0,                                 // No offset
+            std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+        );
+    }
+    crate::items::record_last_item(label, "PlotLine", 1);
+}
+
+/// Append a suffix to the visible part of a label, i.e. before any `##` that starts the hidden
+/// ID part of the label.
+fn with_label_suffix(label: &CString, suffix: &str) -> CString {
+    let label = label.to_string_lossy();
+    let split = label.find("##").unwrap_or(label.len());
+    CString::new(format!("{}{}{}", &label[..split], suffix, &label[split..]))
+        .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label))
+}
+
+/// Find the index ranges of the runs of points in which neither the X nor the Y value is NaN.
+fn nan_separated_segments(x: &[f64], y: &[f64]) -> Vec<std::ops::Range<usize>> {
+    let mut segments = Vec::new();
+    let mut start = None;
+    for i in 0..x.len().min(y.len()) {
+        let is_gap = x[i].is_nan() || y[i].is_nan();
+        match (start, is_gap) {
+            (None, false) => start = Some(i),
+            (Some(segment_start), true) => {
+                segments.push(segment_start..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(segment_start) = start {
+        segments.push(segment_start..x.len().min(y.len()));
+    }
+    segments
+}
+
+/// Compute the centered moving average of `values` over `window` points. The window shrinks
+/// towards the ends of the data, so every output value is an average of the available points
+/// around it. Non-finite values are left out of the averages, and windows without any finite
+/// values average to NaN.
+fn centered_moving_average(values: &[f64], window: usize) -> ScratchBuffer {
+    if window <= 1 {
+        return values.iter().copied().collect();
+    }
+
+    // Running sums make this linear in the number of values, independent of the window size.
+    // They only include finite values, so a NaN or infinity does not spill into later windows.
+    let mut sums = ScratchBuffer::new();
+    let mut sum = 0.0;
+    sums.push(sum);
+    // Number of finite values before each position, which indexes into the running sums
+    let mut finite_before = Vec::with_capacity(values.len() + 1);
+    finite_before.push(0);
+    for value in values {
+        if value.is_finite() {
+            sum += value;
+            sums.push(sum);
+        }
+        finite_before.push(sums.len() - 1);
     }
+
+    let before = (window - 1) / 2;
+    let after = window / 2;
+    (0..values.len())
+        .map(|i| {
+            let start = finite_before[i.saturating_sub(before)];
+            let end = finite_before[(i + after + 1).min(values.len())];
+            match end - start {
+                0 => f64::NAN,
+                count => (sums[end] - sums[start]) / count as f64,
+            }
+        })
+        .collect()
 }
 
 /// Struct to provide functionality for plotting a line in a plot with stairs style.
@@ -113,9 +1044,10 @@ impl PlotStairs {
         if x.len().min(y.len()) == 0 {
             return;
         }
+        crate::extents::track_points(x, y);
         unsafe {
             sys::ImPlot_PlotStairs_doublePtrdoublePtr(
-                self.label.as_ptr() as *const c_char,
+                crate::items::legend_label(&self.label).as_ptr(),
                 x.as_ptr(),
                 y.as_ptr(),
                 x.len().min(y.len()) as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
@@ -124,6 +1056,52 @@ impl PlotStairs {
                 std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
             );
         }
+        crate::items::record_last_item(&self.label, "PlotStairs", x.len().min(y.len()));
+    }
+
+    /// Fill the region between two stairs style lines, for example a quantized uncertainty band
+    /// with the lower bounds `lo` and the upper bounds `hi`. The steps follow the same rule as
+    /// [`PlotStairs::plot`], including `StairsFlags::PRE_STEP`. Only the fill is drawn; to
+    /// outline it, also plot `lo` and `hi` with [`PlotStairs::plot`] using the same label, which
+    /// makes them share the legend entry and color. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    pub fn plot_shaded_between(&self, x: &[f64], lo: &[f64], hi: &[f64]) {
+        let number_of_points = x.len().min(lo.len()).min(hi.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+
+        // Pairs of (x index, value index) for the corners of the steps
+        let corners: Vec<(usize, usize)> = if self.flags.contains(StairsFlags::PRE_STEP) {
+            std::iter::once((0, 0))
+                .chain((1..number_of_points).flat_map(|i| vec![(i - 1, i), (i, i)]))
+                .collect()
+        } else {
+            (0..number_of_points - 1)
+                .flat_map(|i| vec![(i, i), (i + 1, i)])
+                .chain(std::iter::once((number_of_points - 1, number_of_points - 1)))
+                .collect()
+        };
+        let stepped_x: Vec<f64> = corners.iter().map(|(i, _)| x[*i]).collect();
+        let stepped_lo: Vec<f64> = corners.iter().map(|(_, i)| lo[*i]).collect();
+        let stepped_hi: Vec<f64> = corners.iter().map(|(_, i)| hi[*i]).collect();
+
+        crate::extents::track_points(&stepped_x, &stepped_lo);
+        crate::extents::track_points(&stepped_x, &stepped_hi);
+        unsafe {
+            sys::ImPlot_PlotShaded_doublePtrdoublePtrdoublePtr(
+                crate::items::legend_label(&self.label).as_ptr(),
+                stepped_x.as_ptr(),
+                stepped_lo.as_ptr(),
+                stepped_hi.as_ptr(),
+                stepped_x.len() as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                0,                                 // No flags
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+        crate::items::record_last_item(&self.label, "PlotStairs", number_of_points);
     }
 }
 
@@ -134,7 +1112,9 @@ pub struct PlotScatter {
     /// # Panics
     /// Will panic if the label string contains internal null bytes.
     label: CString,
-    flags: ScatterFlags
+    flags: ScatterFlags,
+    /// Stride and offset of the data passed to [`PlotScatter::plot`]
+    layout: DataLayout,
 }
 
 impl PlotScatter {
@@ -147,7 +1127,8 @@ impl PlotScatter {
         Self {
             label: CString::new(label)
                 .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
-            flags
+            flags,
+            layout: DataLayout::default(),
         }
     }
 
@@ -156,36 +1137,208 @@ impl PlotScatter {
         self
     }
 
+    /// Set the distance between consecutive values of the data in **bytes**, see
+    /// [`PlotLine::with_stride`].
+    pub fn with_stride(mut self, bytes: i32) -> Self {
+        self.layout.stride = Some(bytes);
+        self
+    }
+
+    /// Set the index of the value that is plotted first, counted in **values**, see
+    /// [`PlotLine::with_offset`].
+    pub fn with_offset(mut self, count: i32) -> Self {
+        self.layout.offset = count;
+        self
+    }
+
     /// Draw a previously-created scatter plot. Use this in closures passed to
-    /// [`Plot::build()`](struct.Plot.html#method.build)
-    pub fn plot(&self, x: &[f64], y: &[f64]) {
+    /// [`Plot::build()`](struct.Plot.html#method.build). The data can be of any type
+    /// implementing [`ImPlotData`].
+    pub fn plot<T: ImPlotData>(&self, x: &[T], y: &[T]) {
+        self.plot_with_layout(x, y, self.layout);
+    }
+
+    /// Internal helper to plot data prepared by this crate, which ignores the stride and offset
+    /// set on the scatter plot.
+    fn plot_plain<T: ImPlotData>(&self, x: &[T], y: &[T]) {
+        self.plot_with_layout(x, y, DataLayout::default());
+    }
+
+    /// Internal helper to draw a scatter plot from data with the given layout.
+    fn plot_with_layout<T: ImPlotData>(&self, x: &[T], y: &[T], layout: DataLayout) {
+        let number_of_points = layout.point_count(x, y);
         // If there is no data to plot, we stop here
-        if x.len().min(y.len()) == 0 {
+        if number_of_points == 0 {
             return;
         }
+        layout.track_points(x, y, number_of_points);
         unsafe {
-            sys::ImPlot_PlotScatter_doublePtrdoublePtr(
-                self.label.as_ptr() as *const c_char,
+            T::PLOT_SCATTER(
+                crate::items::legend_label(&self.label).as_ptr(),
                 x.as_ptr(),
                 y.as_ptr(),
-                x.len().min(y.len()) as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
                 self.flags.bits() as sys::ImPlotScatterFlags_,
-                0,                           // No offset
-                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+                layout.offset,
+                layout.stride_bytes::<T>(),
             );
         }
+        crate::items::record_last_item(&self.label, "PlotScatter", number_of_points);
     }
-}
 
-/// Struct to provide bar plotting functionality.
-pub struct PlotBars {
-    /// Label to show in the legend for this line
-    label: CString,
+    /// Draw a scatter plot of quantities that carry their unit in their type, converted to values
+    /// in that unit. See [`PlotLine::plot_quantities`] for labeling the axes to match. Use this
+    /// in closures passed to [`Plot::build()`](struct.Plot.html#method.build)
+    pub fn plot_quantities<X: Quantity, Y: Quantity>(&self, x: &[X], y: &[Y]) {
+        self.plot_plain(&quantity_values(x), &quantity_values(y));
+    }
 
-    /// Width of the bars, in plot coordinate terms
-    bar_width: f64,
-    flags: BarsFlags
-}
+    /// Draw a scatter plot of `count` points read from interleaved data, such as a vertex buffer
+    /// laid out as `[x, y, z, x, y, z, ...]`, without deinterleaving it first. The X and Y values
+    /// of the first point are `x_offset` and `y_offset` bytes into `data`, and those of each
+    /// following point are `stride` bytes further. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    ///
+    /// # Panics
+    /// Will panic if the offsets or the stride are not multiples of the size of an `f64`, if
+    /// `data` is too short for `count` points, or if the stride does not fit in an `i32`.
+    pub fn plot_strided(
+        &self,
+        data: &[f64],
+        count: usize,
+        x_offset: usize,
+        y_offset: usize,
+        stride: usize,
+    ) {
+        // If there is no data to plot, we stop here
+        if count == 0 {
+            return;
+        }
+        let stride_i32 = check_strided_layout(data, count, x_offset, y_offset, stride);
+        if crate::extents::wants_points() {
+            crate::extents::track_points(
+                &strided_values(data, count, x_offset, stride),
+                &strided_values(data, count, y_offset, stride),
+            );
+        }
+        unsafe {
+            sys::ImPlot_PlotScatter_doublePtrdoublePtr(
+                crate::items::legend_label(&self.label).as_ptr(),
+                (data.as_ptr() as *const u8).add(x_offset) as *const f64,
+                (data.as_ptr() as *const u8).add(y_offset) as *const f64,
+                count as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                self.flags.bits() as sys::ImPlotScatterFlags_,
+                0, // No offset
+                stride_i32,
+            );
+        }
+        crate::items::record_last_item(&self.label, "PlotScatter", count);
+    }
+
+    /// Draw a scatter plot of `count` points read from interleaved `f32` data, such as a vertex
+    /// buffer, without converting or deinterleaving it first. Offsets and stride are in bytes,
+    /// see [`PlotScatter::plot_strided`]; for vertices of three `f32` values, the stride is 12.
+    /// Use this in closures passed to [`Plot::build()`](struct.Plot.html#method.build)
+    ///
+    /// # Panics
+    /// Will panic if the offsets or the stride are not multiples of the size of an `f32`, if
+    /// `data` is too short for `count` points, or if the stride does not fit in an `i32`.
+    pub fn plot_strided_f32(
+        &self,
+        data: &[f32],
+        count: usize,
+        x_offset: usize,
+        y_offset: usize,
+        stride: usize,
+    ) {
+        // If there is no data to plot, we stop here
+        if count == 0 {
+            return;
+        }
+        let stride_i32 = check_strided_layout(data, count, x_offset, y_offset, stride);
+        if crate::extents::wants_points() {
+            crate::extents::track_points(
+                &strided_values(data, count, x_offset, stride),
+                &strided_values(data, count, y_offset, stride),
+            );
+        }
+        unsafe {
+            sys::ImPlot_PlotScatter_FloatPtrFloatPtr(
+                crate::items::legend_label(&self.label).as_ptr(),
+                (data.as_ptr() as *const u8).add(x_offset) as *const f32,
+                (data.as_ptr() as *const u8).add(y_offset) as *const f32,
+                count as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                self.flags.bits() as sys::ImPlotScatterFlags_,
+                0, // No offset
+                stride_i32,
+            );
+        }
+        crate::items::record_last_item(&self.label, "PlotScatter", count);
+    }
+}
+
+/// Internal helper to check that `count` points with the given byte offsets and stride can be
+/// read from `data`, which has to hold at least one point, and to convert the stride to the type
+/// ImPlot takes.
+fn check_strided_layout<T>(
+    data: &[T],
+    count: usize,
+    x_offset: usize,
+    y_offset: usize,
+    stride: usize,
+) -> i32 {
+    let size = std::mem::size_of::<T>();
+    assert!(
+        x_offset.is_multiple_of(size)
+            && y_offset.is_multiple_of(size)
+            && stride.is_multiple_of(size),
+        "Offsets {} and {} and stride {} are not all multiples of the value size of {} bytes",
+        x_offset,
+        y_offset,
+        stride,
+        size
+    );
+    let needed = (count - 1) * stride + x_offset.max(y_offset) + size;
+    assert!(
+        needed <= std::mem::size_of_val(data),
+        "Strided data needs {} bytes for {} points, got {}",
+        needed,
+        count,
+        std::mem::size_of_val(data)
+    );
+    i32::try_from(stride).expect("Stride does not fit in an i32")
+}
+
+/// Internal helper to read `count` values spaced `stride` bytes apart, starting `offset` bytes
+/// into `data`, as checked by [`check_strided_layout`].
+fn strided_values<T: Copy + Into<f64>>(
+    data: &[T],
+    count: usize,
+    offset: usize,
+    stride: usize,
+) -> ScratchBuffer {
+    let size = std::mem::size_of::<T>();
+    (0..count)
+        .map(|i| data[(offset + i * stride) / size].into())
+        .collect()
+}
+
+/// Struct to provide bar plotting functionality.
+pub struct PlotBars {
+    /// Label to show in the legend for this line
+    label: CString,
+
+    /// Width of the bars, in plot coordinate terms
+    bar_width: f64,
+
+    /// Width of the bars as a fraction of the spacing of their positions, if set. This takes
+    /// precedence over `bar_width`.
+    bar_width_fraction: Option<f64>,
+    flags: BarsFlags,
+
+    /// Stride and offset of the data passed to [`PlotBars::plot`]
+    layout: DataLayout,
+}
 
 impl PlotBars {
     /// Create a new bar plot to be shown. Defaults to drawing vertical bars.
@@ -202,27 +1355,118 @@ impl PlotBars {
             label: CString::new(label)
                 .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
             bar_width: 0.67, // Default value taken from C++ implot
-            flags
+            bar_width_fraction: None,
+            flags,
+            layout: DataLayout::default(),
         }
     }
 
     /// Set the width of the bars
     pub fn with_bar_width(mut self, bar_width: f64) -> Self {
         self.bar_width = bar_width;
+        self.bar_width_fraction = None;
         self
     }
 
+    /// Set the width of the bars as a fraction of the spacing of their positions, for example
+    /// 0.8 for bars that take up 80% of the space from one position to the next. The absolute
+    /// width is computed from the positions passed to [`PlotBars::plot`] on every call. If the
+    /// positions are not evenly spaced, the smallest gap between two neighboring positions is
+    /// used, so no bars overlap. With fewer than two distinct positions, a spacing of 1 is
+    /// assumed, which is the spacing of [`PlotBars::plot_categorical`].
+    pub fn with_bar_width_fraction(mut self, fraction: f64) -> Self {
+        self.bar_width_fraction = Some(fraction);
+        self
+    }
+
+    /// Internal helper to get the absolute width of `count` bars drawn at the given positions.
+    fn bar_width_for<T: ImPlotData>(
+        &self,
+        axis_positions: &[T],
+        count: usize,
+        layout: DataLayout,
+    ) -> f64 {
+        match self.bar_width_fraction {
+            Some(fraction) => {
+                fraction * minimum_spacing(&layout.values(axis_positions, count)).unwrap_or(1.0)
+            }
+            None => self.bar_width,
+        }
+    }
+
     pub fn flags(mut self, flags: BarsFlags) -> Self {
         self.flags = flags;
         self
     }
 
+    /// Set the distance between consecutive values of the data in **bytes**, see
+    /// [`PlotLine::with_stride`].
+    pub fn with_stride(mut self, bytes: i32) -> Self {
+        self.layout.stride = Some(bytes);
+        self
+    }
+
+    /// Set the index of the value that is plotted first, counted in **values**, see
+    /// [`PlotLine::with_offset`].
+    pub fn with_offset(mut self, count: i32) -> Self {
+        self.layout.offset = count;
+        self
+    }
+
+    /// Draw bars over string categories, at positions 0 to n - 1 in the order the categories are
+    /// given in, and label the ticks at those positions with the categories. The ticks are on the
+    /// first X axis, or the first Y axis for horizontal bars, and are left out if custom ticks
+    /// are set on the plot for that axis. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build).
+    ///
+    /// Ticks can only be set up before anything is plotted, so the ticks are set up from the
+    /// categories when the plot is begun on the next frame, and there are no category ticks on
+    /// the first frame. The categories are stored per plot title, so plots using this should
+    /// have unique titles.
+    ///
+    /// # Panics
+    /// Will panic if any of the category strings contain internal null bytes.
+    pub fn plot_categorical(&self, categories: &[&str], values: &[f64]) {
+        let number_of_points = categories.len().min(values.len());
+        let labels = categories[..number_of_points]
+            .iter()
+            .map(|category| {
+                CString::new(*category)
+                    .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", category))
+            })
+            .collect();
+        let axis = if self.flags.contains(BarsFlags::HORIZONTAL) {
+            Axis::Y1
+        } else {
+            Axis::X1
+        };
+        crate::plot_state::store_category_ticks(axis, labels);
+
+        let positions: Vec<f64> = (0..number_of_points).map(|i| i as f64).collect();
+        self.plot_with_layout(
+            &positions,
+            &values[..number_of_points],
+            DataLayout::default(),
+        );
+    }
+
     /// Draw a previously-created bar plot. Use this in closures passed to
     /// [`Plot::build()`](struct.Plot.html#method.build). The `axis_positions`
     /// specify where on the corresponding axis (X for vertical mode, Y for horizontal mode) the
-    /// bar is drawn, and the `bar_values` specify what values the bars have.
-    pub fn plot(&self, axis_positions: &[f64], bar_values: &[f64]) {
-        let number_of_points = axis_positions.len().min(bar_values.len());
+    /// bar is drawn, and the `bar_values` specify what values the bars have. The data can be of
+    /// any type implementing [`ImPlotData`].
+    pub fn plot<T: ImPlotData>(&self, axis_positions: &[T], bar_values: &[T]) {
+        self.plot_with_layout(axis_positions, bar_values, self.layout);
+    }
+
+    /// Internal helper to draw a bar plot from data with the given layout.
+    fn plot_with_layout<T: ImPlotData>(
+        &self,
+        axis_positions: &[T],
+        bar_values: &[T],
+        layout: DataLayout,
+    ) {
+        let number_of_points = layout.point_count(axis_positions, bar_values);
         // If there is no data to plot, we stop here
         if number_of_points == 0 {
             return;
@@ -240,21 +1484,39 @@ impl PlotBars {
                 x = axis_positions;
                 y = bar_values;
             };
+            layout.track_points(x, y, number_of_points);
 
-            sys::ImPlot_PlotBars_doublePtrdoublePtr(
-                self.label.as_ptr() as *const c_char,
+            T::PLOT_BARS(
+                crate::items::legend_label(&self.label).as_ptr(),
                 x.as_ptr(),
                 y.as_ptr(),
                 number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
-                self.bar_width,
+                self.bar_width_for(axis_positions, number_of_points, layout),
                 self.flags.bits() as sys::ImPlotBarsFlags_,
-                0,                                 // No offset
-                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+                layout.offset,
+                layout.stride_bytes::<T>(),
             );
         }
+        crate::items::record_last_item(&self.label, "PlotBars", number_of_points);
     }
 }
 
+/// Internal helper to get the smallest gap between two neighboring distinct finite positions,
+/// or `None` if there are fewer than two of them.
+fn minimum_spacing(positions: &[f64]) -> Option<f64> {
+    let mut sorted: ScratchBuffer = positions
+        .iter()
+        .copied()
+        .filter(|x| x.is_finite())
+        .collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    sorted
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .filter(|gap| *gap > 0.0)
+        .min_by(|a, b| a.partial_cmp(b).unwrap())
+}
+
 /// Struct to provide functionality for adding text within a plot
 pub struct PlotText {
     /// Label to show in plot
@@ -310,6 +1572,7 @@ impl PlotText {
             return;
         }
 
+        crate::extents::track_points(&[x], &[y]);
         unsafe {
             sys::ImPlot_PlotText(
                 self.label.as_ptr() as *const c_char,
@@ -322,6 +1585,9 @@ impl PlotText {
                 self.flags.bits() as sys::ImPlotTextFlags_,
             );
         }
+        // Text is not recorded as an item, so its position must not end up in the next item's
+        // bounds
+        crate::items::discard_pending_bounds();
     }
 }
 
@@ -340,11 +1606,22 @@ pub struct PlotHeatmap {
     /// that is what's needed for the plot call anyway. Conversion is done in the setter.
     label_format: Option<CString>,
 
+    /// Whether the cells are labeled with their values formatted by `auto_format` instead of
+    /// with `label_format`
+    auto_labels: bool,
+
     /// Lower left point for the bounding rectangle. This is called `bounds_min` in the C++ code.
     drawarea_lower_left: ImPlotPoint,
 
     /// Upper right point for the bounding rectangle. This is called `bounds_max` in the C++ code.
     drawarea_upper_right: ImPlotPoint,
+
+    /// Colormap to use for this heatmap only. If this is set to `None`, whatever colormap is
+    /// currently active is used.
+    colormap: Option<Colormap>,
+
+    /// Colors to tint cells with values below and above the scale range with, if any
+    out_of_range_colors: Option<(ImVec4, ImVec4)>,
     flags: HeatmapFlags
 }
 
@@ -363,8 +1640,11 @@ impl PlotHeatmap {
                 .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
             scale_range: None,
             label_format: Some(CString::new("%.1f").unwrap()),
+            auto_labels: false,
             drawarea_lower_left: ImPlotPoint { X: 0.0, Y: 0.0 },
             drawarea_upper_right: ImPlotPoint { X: 1.0, Y: 1.0 },
+            colormap: None,
+            out_of_range_colors: None,
             flags
         }
     }
@@ -397,9 +1677,128 @@ impl PlotHeatmap {
             CString::new(x)
                 .unwrap_or_else(|_| panic!("Format label string has internal null bytes: {}", x))
         });
+        self.auto_labels = false;
+        self
+    }
+
+    /// Label the cells with their values formatted by [`crate::auto_format`], which switches
+    /// between fixed and scientific notation depending on the magnitude of each value, instead
+    /// of with a format string. ImPlot only takes format strings for heatmap labels, so the
+    /// labels are drawn as text on top of the heatmap, in the plot's inlay text color rather
+    /// than in a color contrasting each cell. This replaces the label format.
+    pub fn with_auto_labels(mut self) -> Self {
+        self.label_format = None;
+        self.auto_labels = true;
+        self
+    }
+
+    /// Internal helper to draw the labels of the cells when auto labels are enabled, with
+    /// `value` giving the value at an index into the data.
+    fn maybe_draw_auto_labels<F: Fn(usize) -> f64>(
+        &self,
+        number_of_rows: u32,
+        number_of_cols: u32,
+        value: F,
+    ) {
+        if !self.auto_labels {
+            return;
+        }
+
+        let (rows, cols) = (number_of_rows as usize, number_of_cols as usize);
+        let cell_width = (self.drawarea_upper_right.X - self.drawarea_lower_left.X) / cols as f64;
+        let cell_height = (self.drawarea_upper_right.Y - self.drawarea_lower_left.Y) / rows as f64;
+        let col_major = self.flags.contains(HeatmapFlags::COL_MAJOR);
+        let mut buf = [0u8; 32];
+        for row in 0..rows {
+            for col in 0..cols {
+                let index = if col_major {
+                    col * rows + row
+                } else {
+                    row * cols + col
+                };
+                crate::auto_format(value(index), &mut buf);
+                // Rows go from the top of the drawing area to the bottom, like in ImPlot
+                unsafe {
+                    sys::ImPlot_PlotText(
+                        buf.as_ptr() as *const c_char,
+                        self.drawarea_lower_left.X + (col as f64 + 0.5) * cell_width,
+                        self.drawarea_upper_right.Y - (row as f64 + 0.5) * cell_height,
+                        sys::ImVec2 { x: 0.0, y: 0.0 },
+                        0,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Tint the cells with values below the scale range in `under` and those above it in `over`,
+    /// for example to flag saturated sensor pixels. ImPlot draws such cells in the colors of the
+    /// ends of the colormap, like the values at the ends of the range, so they cannot be told
+    /// apart otherwise. The tints are drawn on top of the cells, so use colors with an alpha
+    /// below one to keep the underlying colors visible. This only has an effect with a fixed
+    /// scale set with [`PlotHeatmap::with_scale`], since an automatic scale spans all values.
+    ///
+    /// Every cell is checked on every frame, and each cell outside of the range adds a rectangle
+    /// to the plot's draw list. For large grids with many cells outside of the range, this costs
+    /// about as much as drawing the heatmap a second time.
+    pub fn with_out_of_range_colors(mut self, under: ImVec4, over: ImVec4) -> Self {
+        self.out_of_range_colors = Some((under, over));
         self
     }
 
+    /// Internal helper to tint the cells outside of the scale range when out of range colors
+    /// are set, with `value` giving the value at an index into the data.
+    fn maybe_draw_out_of_range<F: Fn(usize) -> f64>(
+        &self,
+        number_of_rows: u32,
+        number_of_cols: u32,
+        value: F,
+    ) {
+        let ((under, over), (scale_min, scale_max)) =
+            match (self.out_of_range_colors, self.scale_range) {
+                (Some(colors), Some(scale_range)) => (colors, scale_range),
+                _ => return,
+            };
+
+        let (rows, cols) = (number_of_rows as usize, number_of_cols as usize);
+        let cell_width = (self.drawarea_upper_right.X - self.drawarea_lower_left.X) / cols as f64;
+        let cell_height = (self.drawarea_upper_right.Y - self.drawarea_lower_left.Y) / rows as f64;
+        let col_major = self.flags.contains(HeatmapFlags::COL_MAJOR);
+        let (under, over) = (color_to_u32(under), color_to_u32(over));
+        draw_clipped(|draw_list| {
+            for row in 0..rows {
+                for col in 0..cols {
+                    let index = if col_major {
+                        col * rows + row
+                    } else {
+                        row * cols + col
+                    };
+                    let cell_value = value(index);
+                    let color = if cell_value < scale_min {
+                        under
+                    } else if cell_value > scale_max {
+                        over
+                    } else {
+                        continue;
+                    };
+                    // Rows go from the top of the drawing area to the bottom, like in ImPlot
+                    let left = self.drawarea_lower_left.X + col as f64 * cell_width;
+                    let top = self.drawarea_upper_right.Y - row as f64 * cell_height;
+                    unsafe {
+                        sys::ImDrawList_AddRectFilled(
+                            draw_list,
+                            to_pixels(left, top),
+                            to_pixels(left + cell_width, top - cell_height),
+                            color,
+                            0.0,
+                            0,
+                        );
+                    }
+                }
+            }
+        });
+    }
+
     /// Specify the drawing area as the lower left and upper right point
     pub fn with_drawing_area(mut self, lower_left: ImPlotPoint, upper_right: ImPlotPoint) -> Self {
         self.drawarea_lower_left = lower_left;
@@ -407,6 +1806,15 @@ impl PlotHeatmap {
         self
     }
 
+    /// Specify a colormap to be used for this heatmap only. The colormap is pushed right before
+    /// the heatmap is drawn and popped again right afterwards, so other plot elements are not
+    /// affected by it.
+    #[rustversion::attr(since(1.48), doc(alias = "PushColormap"))]
+    pub fn with_colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = Some(colormap);
+        self
+    }
+
     /// Plot the heatmap, with the given values (assumed to be in row-major order),
     /// number of rows and number of columns.
     pub fn plot(&self, values: &[f64], number_of_rows: u32, number_of_cols: u32) {
@@ -421,9 +1829,14 @@ impl PlotHeatmap {
             (min_seen, max_seen)
         });
 
+        crate::extents::track_points(
+            &[self.drawarea_lower_left.X, self.drawarea_upper_right.X],
+            &[self.drawarea_lower_left.Y, self.drawarea_upper_right.Y],
+        );
+        let colormap_token = self.colormap.map(push_colormap_from_preset);
         unsafe {
             sys::ImPlot_PlotHeatmap_doublePtr(
-                self.label.as_ptr() as *const c_char,
+                crate::items::legend_label(&self.label).as_ptr(),
                 values.as_ptr(),
                 number_of_rows as i32, // Not sure why C++ code uses a signed value here
                 number_of_cols as i32, // Not sure why C++ code uses a signed value here
@@ -441,61 +1854,1579 @@ impl PlotHeatmap {
                 self.flags.bits() as sys::ImPlotHeatmapFlags_
             );
         }
+        crate::items::record_last_item(
+            &self.label,
+            "PlotHeatmap",
+            number_of_rows as usize * number_of_cols as usize,
+        );
+        self.maybe_draw_out_of_range(number_of_rows, number_of_cols, |i| values[i]);
+        self.maybe_draw_auto_labels(number_of_rows, number_of_cols, |i| values[i]);
+        if let Some(token) = colormap_token {
+            token.pop();
+        }
+    }
+
+    /// Plot the heatmap from 8-bit values, such as an intensity image, without converting them to
+    /// `f64` first. Unless a scale range was set with [`PlotHeatmap::with_scale`], the scale spans
+    /// the full range of `u8`, i.e. 0 to 255. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    ///
+    /// # Panics
+    /// Will panic if `values` holds fewer than `number_of_rows * number_of_cols` values.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotHeatmap"))]
+    pub fn plot_u8(&self, values: &[u8], number_of_rows: u32, number_of_cols: u32) {
+        let number_of_values = number_of_rows as usize * number_of_cols as usize;
+        assert!(
+            values.len() >= number_of_values,
+            "Heatmap needs {} values for {} rows and {} columns, got {}",
+            number_of_values,
+            number_of_rows,
+            number_of_cols,
+            values.len()
+        );
+        let scale_range = self.scale_range.unwrap_or((0.0, u8::MAX as f64));
+
+        crate::extents::track_points(
+            &[self.drawarea_lower_left.X, self.drawarea_upper_right.X],
+            &[self.drawarea_lower_left.Y, self.drawarea_upper_right.Y],
+        );
+        let colormap_token = self.colormap.map(push_colormap_from_preset);
+        unsafe {
+            sys::ImPlot_PlotHeatmap_U8Ptr(
+                crate::items::legend_label(&self.label).as_ptr(),
+                values.as_ptr(),
+                number_of_rows as i32, // Not sure why C++ code uses a signed value here
+                number_of_cols as i32, // Not sure why C++ code uses a signed value here
+                scale_range.0,
+                scale_range.1,
+                match &self.label_format {
+                    Some(label_format) => label_format.as_ptr() as *const c_char,
+                    None => std::ptr::null(),
+                },
+                self.drawarea_lower_left,
+                self.drawarea_upper_right,
+                self.flags.bits() as sys::ImPlotHeatmapFlags_
+            );
+        }
+        crate::items::record_last_item(&self.label, "PlotHeatmap", number_of_values);
+        self.maybe_draw_out_of_range(number_of_rows, number_of_cols, |i| values[i] as f64);
+        self.maybe_draw_auto_labels(number_of_rows, number_of_cols, |i| values[i] as f64);
+        if let Some(token) = colormap_token {
+            token.pop();
+        }
     }
 }
 
-/// Struct to provide stem plotting functionality.
-pub struct PlotStems {
-    /// Label to show in the legend for this line
+/// Helper for showing several heatmaps with one common color scale, so their colors can be
+/// compared directly. The scale spans the values of all heatmaps; apply it to each heatmap with
+/// [`SharedColorScale::apply`] and show a single colorbar for all of them with
+/// [`SharedColorScale::draw_colorbar`].
+#[derive(Copy, Clone, Debug)]
+pub struct SharedColorScale {
+    /// Minimum of the scale
+    scale_min: f64,
+    /// Maximum of the scale
+    scale_max: f64,
+    /// Colormap used for the heatmaps and the colorbar. If this is `None`, whatever colormap is
+    /// currently active is used.
+    colormap: Option<Colormap>,
+}
+
+impl SharedColorScale {
+    /// Create a scale spanning all finite values in the given slices, e.g. one slice per
+    /// heatmap. If there are no finite values, the scale spans 0 to 1.
+    pub fn from_values(values: &[&[f64]]) -> Self {
+        let (scale_min, scale_max) = values
+            .iter()
+            .flat_map(|values| values.iter())
+            .filter(|value| value.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(*value), max.max(*value))
+            });
+        let (scale_min, scale_max) = if scale_min <= scale_max {
+            (scale_min, scale_max)
+        } else {
+            (0.0, 1.0)
+        };
+        Self {
+            scale_min,
+            scale_max,
+            colormap: None,
+        }
+    }
+
+    /// Use the given colormap for all heatmaps this scale is applied to, and for the colorbar.
+    pub fn with_colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = Some(colormap);
+        self
+    }
+
+    /// Get the range of the scale, as `(minimum, maximum)`.
+    pub fn range(&self) -> (f64, f64) {
+        (self.scale_min, self.scale_max)
+    }
+
+    /// Set the scale, and the colormap if one was chosen, on the given heatmap.
+    pub fn apply(&self, heatmap: PlotHeatmap) -> PlotHeatmap {
+        let heatmap = heatmap.with_scale(self.scale_min, self.scale_max);
+        match self.colormap {
+            Some(colormap) => heatmap.with_colormap(colormap),
+            None => heatmap,
+        }
+    }
+
+    /// Draw a colorbar for this scale, see [`colormap_scale`](crate::colormap_scale). Call this
+    /// outside of plots, for example next to the last of the heatmaps.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn draw_colorbar(&self, label: &str, size: [f32; 2]) {
+        crate::colormap_scale(label, self.scale_min, self.scale_max, size, self.colormap);
+    }
+}
+
+/// Struct to provide functionality for plotting the area between a line and a horizontal
+/// reference value as a filled region. For filling the area between two lines, see
+/// [`PlotConfidenceBand`] or [`plot_comparison`].
+pub struct PlotShaded {
+    /// Label to show in the legend for this filled region
     label: CString,
 
-    /// Reference value for the y value, which the stems are "with respect to"
+    /// Y value the region extends to from the line
     reference_y: f64,
-    flags: StemsFlags
+    flags: ShadedFlags,
 }
 
-impl PlotStems {
-    /// Create a new stem plot to be shown. Does not draw anything by itself, call
-    /// [`PlotStems::plot`] on the struct for that.
+impl PlotShaded {
+    /// Create a new filled region to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
     pub fn new(label: &str) -> Self {
-        Self::new_with_flags(label, StemsFlags::empty())
+        Self::new_with_flags(label, ShadedFlags::empty())
     }
 
-    pub fn new_with_flags(label: &str, flags: StemsFlags) -> Self {
+    /// Create a new filled region to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new_with_flags(label: &str, flags: ShadedFlags) -> Self {
         Self {
             label: CString::new(label)
                 .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
             reference_y: 0.0, // Default value taken from C++ implot
-            flags
+            flags,
         }
     }
 
-    /// Set the reference y value for the stems
+    pub fn flags(mut self, flags: ShadedFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Set the Y value the region extends to from the line. Infinite values fill the region up
+    /// to the top or bottom of the plot area, whatever the axis limits are.
     pub fn with_reference_y(mut self, reference_y: f64) -> Self {
         self.reference_y = reference_y;
         self
     }
 
-    /// Draw a previously-created stem plot. Use this in closures passed to
-    /// [`Plot::build()`](struct.Plot.html#method.build). The `axis_positions` specify where on the
-    /// X axis the stems are drawn, and the `stem_values` specify what values the stems have.
-    pub fn plot(&self, axis_positions: &[f64], stem_values: &[f64]) {
-        let number_of_points = axis_positions.len().min(stem_values.len());
+    /// Draw the region between the line through `x` and `y` and the reference value. Use this
+    /// in closures passed to [`Plot::build()`](struct.Plot.html#method.build). The region is
+    /// drawn with the fill color and alpha of the current style, see `PlotColorElement::Fill`
+    /// and `StyleVar::FillAlpha`.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotShaded"))]
+    pub fn plot(&self, x: &[f64], y: &[f64]) {
+        let number_of_points = x.len().min(y.len());
         // If there is no data to plot, we stop here
         if number_of_points == 0 {
             return;
         }
+        crate::extents::track_points(x, y);
+        if self.reference_y.is_finite() {
+            let reference: ScratchBuffer = x.iter().map(|_| self.reference_y).collect();
+            crate::extents::track_points(x, &reference);
+        }
         unsafe {
-            sys::ImPlot_PlotStems_doublePtrdoublePtr(
-                self.label.as_ptr() as *const c_char,
-                axis_positions.as_ptr(),
-                stem_values.as_ptr(),
+            sys::ImPlot_PlotShaded_doublePtrdoublePtrInt(
+                crate::items::legend_label(&self.label).as_ptr(),
+                x.as_ptr(),
+                y.as_ptr(),
                 number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
                 self.reference_y,
-                self.flags.bits() as sys::ImPlotStemsFlags_,
+                self.flags.bits() as sys::ImPlotShadedFlags_,
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+        crate::items::record_last_item(&self.label, "PlotShaded", number_of_points);
+    }
+
+    /// Draw the region between the lines through `x` and `y1` and through `x` and `y2`, for
+    /// example the band between an upper and a lower bound. The reference value is not used for
+    /// this. Use this in closures passed to [`Plot::build()`](struct.Plot.html#method.build).
+    #[rustversion::attr(since(1.48), doc(alias = "PlotShaded"))]
+    pub fn plot_between(&self, x: &[f64], y1: &[f64], y2: &[f64]) {
+        let number_of_points = x.len().min(y1.len()).min(y2.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+        let x = &x[..number_of_points];
+        crate::extents::track_points(x, y1);
+        crate::extents::track_points(x, y2);
+        unsafe {
+            sys::ImPlot_PlotShaded_doublePtrdoublePtrdoublePtr(
+                crate::items::legend_label(&self.label).as_ptr(),
+                x.as_ptr(),
+                y1.as_ptr(),
+                y2.as_ptr(),
+                number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                self.flags.bits() as sys::ImPlotShadedFlags_,
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+        crate::items::record_last_item(&self.label, "PlotShaded", number_of_points);
+    }
+}
+
+/// Struct to provide functionality for plotting error bars, for example to show the uncertainty
+/// of measurements drawn as a line or a scatter plot with the same data.
+pub struct PlotErrorBars {
+    /// Label to show in the legend for these error bars
+    label: CString,
+    flags: ErrorBarsFlags,
+}
+
+impl PlotErrorBars {
+    /// Create new error bars to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self::new_with_flags(label, ErrorBarsFlags::empty())
+    }
+
+    /// Create new error bars to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new_with_flags(label: &str, flags: ErrorBarsFlags) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            flags,
+        }
+    }
+
+    pub fn flags(mut self, flags: ErrorBarsFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Draw symmetric error bars around the points given by `x` and `y`, each reaching `err`
+    /// above and below its point, or to the left and right with `ErrorBarsFlags::HORIZONTAL`.
+    /// Use this in closures passed to [`Plot::build()`](struct.Plot.html#method.build).
+    #[rustversion::attr(since(1.48), doc(alias = "PlotErrorBars"))]
+    pub fn plot(&self, x: &[f64], y: &[f64], err: &[f64]) {
+        let number_of_points = x.len().min(y.len()).min(err.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+        let (x, y, err) = (
+            &x[..number_of_points],
+            &y[..number_of_points],
+            &err[..number_of_points],
+        );
+        self.track_bar_ends(x, y, err, err);
+        unsafe {
+            sys::ImPlot_PlotErrorBars_doublePtrdoublePtrdoublePtrInt(
+                crate::items::legend_label(&self.label).as_ptr(),
+                x.as_ptr(),
+                y.as_ptr(),
+                err.as_ptr(),
+                number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                self.flags.bits() as sys::ImPlotErrorBarsFlags_,
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+        crate::items::record_last_item(&self.label, "PlotErrorBars", number_of_points);
+    }
+
+    /// Draw asymmetric error bars around the points given by `x` and `y`. The bars reach `neg`
+    /// below and `pos` above each point, or to its left and right with
+    /// `ErrorBarsFlags::HORIZONTAL`. Both are magnitudes, so they should not be negative: a
+    /// point at `y = 1.0` with `neg = 0.2` and `pos = 0.5` gets a bar from 0.8 to 1.5. Use this
+    /// in closures passed to [`Plot::build()`](struct.Plot.html#method.build).
+    #[rustversion::attr(since(1.48), doc(alias = "PlotErrorBars"))]
+    pub fn plot_asymmetric(&self, x: &[f64], y: &[f64], neg: &[f64], pos: &[f64]) {
+        let number_of_points = x.len().min(y.len()).min(neg.len()).min(pos.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+        let (x, y, neg, pos) = (
+            &x[..number_of_points],
+            &y[..number_of_points],
+            &neg[..number_of_points],
+            &pos[..number_of_points],
+        );
+        self.track_bar_ends(x, y, neg, pos);
+        unsafe {
+            sys::ImPlot_PlotErrorBars_doublePtrdoublePtrdoublePtrdoublePtr(
+                crate::items::legend_label(&self.label).as_ptr(),
+                x.as_ptr(),
+                y.as_ptr(),
+                neg.as_ptr(),
+                pos.as_ptr(),
+                number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                self.flags.bits() as sys::ImPlotErrorBarsFlags_,
                 0,                                 // No offset
                 std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
             );
         }
+        crate::items::record_last_item(&self.label, "PlotErrorBars", number_of_points);
+    }
+
+    /// Internal helper to track the ends of the bars in the data extents, since they span the
+    /// area the bars cover. Unlike for [`PlotBars`], ImPlot takes the point coordinates the same
+    /// way for both orientations, only the direction the bars extend in changes.
+    fn track_bar_ends(&self, x: &[f64], y: &[f64], neg: &[f64], pos: &[f64]) {
+        let horizontal = self.flags.contains(ErrorBarsFlags::HORIZONTAL);
+        let (values, positions) = if horizontal { (x, y) } else { (y, x) };
+        let lower: ScratchBuffer = values.iter().zip(neg).map(|(v, e)| v - e).collect();
+        let upper: ScratchBuffer = values.iter().zip(pos).map(|(v, e)| v + e).collect();
+        for ends in [lower, upper].iter() {
+            if horizontal {
+                crate::extents::track_points(ends, positions);
+            } else {
+                crate::extents::track_points(positions, ends);
+            }
+        }
+    }
+}
+
+/// Struct to provide functionality for plotting digital signals, such as the channels of a logic
+/// analyzer. ImPlot draws digital signals as stacked traces at the bottom of the plot area, with
+/// a fixed height in pixels per channel regardless of the Y axis, see
+/// `StyleVar::DigitalBitHeight` and `StyleVar::DigitalBitGap`.
+pub struct PlotDigital {
+    /// Label to show in the legend for this signal
+    label: CString,
+    flags: DigitalFlags,
+}
+
+impl PlotDigital {
+    /// Create a new digital signal to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self::new_with_flags(label, DigitalFlags::empty())
+    }
+
+    /// Create a new digital signal to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new_with_flags(label: &str, flags: DigitalFlags) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            flags,
+        }
+    }
+
+    pub fn flags(mut self, flags: DigitalFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Draw the signal, which has the value `y` from each time `x` on until the next one. Values
+    /// other than zero are drawn as high. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build).
+    #[rustversion::attr(since(1.48), doc(alias = "PlotDigital"))]
+    pub fn plot(&self, x: &[f64], y: &[f64]) {
+        let number_of_points = x.len().min(y.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+        crate::extents::track_points(x, y);
+        unsafe {
+            sys::ImPlot_PlotDigital_doublePtr(
+                crate::items::legend_label(&self.label).as_ptr(),
+                x.as_ptr(),
+                y.as_ptr(),
+                number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                self.flags.bits() as sys::ImPlotDigitalFlags_,
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+        crate::items::record_last_item(&self.label, "PlotDigital", number_of_points);
+    }
+
+    /// Draw a signal given as booleans, where `true` is high and `false` is low, see
+    /// [`PlotDigital::plot`]. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build).
+    pub fn plot_bools(&self, x: &[f64], values: &[bool]) {
+        let y: ScratchBuffer = values
+            .iter()
+            .map(|&value| if value { 1.0 } else { 0.0 })
+            .collect();
+        self.plot(x, &y);
+    }
+}
+
+/// Struct to provide functionality for drawing an image into a plot, stretched over a rectangle
+/// in plot coordinates, for example a map or a camera frame behind the data plotted on top of it.
+pub struct PlotImage {
+    /// Label to show in the legend for this image
+    label: CString,
+    /// Texture of the image, as registered with the imgui renderer
+    texture_id: imgui::TextureId,
+    /// Corner of the image at the smallest X and Y values, in plot coordinates
+    bounds_min: ImPlotPoint,
+    /// Corner of the image at the largest X and Y values, in plot coordinates
+    bounds_max: ImPlotPoint,
+    /// Texture coordinates at `bounds_min` and `bounds_max`
+    uv: (ImVec2, ImVec2),
+    /// Color the image is multiplied with
+    tint: ImVec4,
+    flags: ImageFlags,
+}
+
+impl PlotImage {
+    /// Create a new image to be plotted, covering the rectangle from `bounds_min` to
+    /// `bounds_max` in plot coordinates. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(
+        label: &str,
+        texture_id: imgui::TextureId,
+        bounds_min: ImPlotPoint,
+        bounds_max: ImPlotPoint,
+    ) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            texture_id,
+            bounds_min,
+            bounds_max,
+            // Default values taken from C++ implot
+            uv: (ImVec2 { x: 0.0, y: 0.0 }, ImVec2 { x: 1.0, y: 1.0 }),
+            tint: ImVec4 { x: 1.0, y: 1.0, z: 1.0, w: 1.0 },
+            flags: ImageFlags::empty(),
+        }
+    }
+
+    pub fn flags(mut self, flags: ImageFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Set the texture coordinates at the corners of the image, for showing only part of the
+    /// texture or flipping it. `uv0` is used at `bounds_min` and `uv1` at `bounds_max`. Defaults
+    /// to the whole texture, from `(0, 0)` to `(1, 1)`.
+    pub fn with_uv(mut self, uv0: ImVec2, uv1: ImVec2) -> Self {
+        self.uv = (uv0, uv1);
+        self
+    }
+
+    /// Set the color the image is multiplied with, for example to fade it out behind the data
+    /// with a lower alpha. Defaults to opaque white, which leaves the image unchanged.
+    pub fn with_tint(mut self, tint: ImVec4) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    /// Draw the image. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build). Items are drawn in the order they are
+    /// plotted in, so plot the image first to have it behind the data.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotImage"))]
+    pub fn plot(&self) {
+        crate::extents::track_points(
+            &[self.bounds_min.X, self.bounds_max.X],
+            &[self.bounds_min.Y, self.bounds_max.Y],
+        );
+        unsafe {
+            sys::ImPlot_PlotImage(
+                crate::items::legend_label(&self.label).as_ptr(),
+                self.texture_id.id() as sys::ImTextureID,
+                self.bounds_min,
+                self.bounds_max,
+                self.uv.0,
+                self.uv.1,
+                self.tint,
+                self.flags.bits() as sys::ImPlotImageFlags_,
+            );
+        }
+        crate::items::record_last_item(&self.label, "PlotImage", 1);
+    }
+}
+
+/// Struct to provide functionality for plotting infinite reference lines, for example to mark
+/// thresholds. The lines span the whole plot area and can not be dragged; for a line the user
+/// can move, see [`crate::drag_line_y`].
+pub struct PlotInfLines {
+    /// Label to show in the legend for these lines
+    label: CString,
+    flags: InfLinesFlags,
+}
+
+impl PlotInfLines {
+    /// Create new infinite lines to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self::new_with_flags(label, InfLinesFlags::empty())
+    }
+
+    /// Create new infinite lines to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new_with_flags(label: &str, flags: InfLinesFlags) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            flags,
+        }
+    }
+
+    pub fn flags(mut self, flags: InfLinesFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Draw a vertical line at each of the given X positions, or a horizontal line at each of
+    /// the given Y positions with `InfLinesFlags::HORIZONTAL`. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build).
+    #[rustversion::attr(since(1.48), doc(alias = "PlotInfLines"))]
+    pub fn plot(&self, positions: &[f64]) {
+        // If there is no data to plot, we stop here
+        if positions.is_empty() {
+            return;
+        }
+        // The lines have no extent along their own direction, so there are no points to track
+        unsafe {
+            sys::ImPlot_PlotInfLines_doublePtr(
+                crate::items::legend_label(&self.label).as_ptr(),
+                positions.as_ptr(),
+                positions.len() as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                self.flags.bits() as sys::ImPlotInfLinesFlags_,
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+        crate::items::record_last_item(&self.label, "PlotInfLines", positions.len());
+    }
+}
+
+/// How the number of bins of a histogram is chosen, see [`PlotHistogram::with_bins`]. The
+/// automatic methods derive it from the number of values, see their ImPlot documentation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HistogramBins {
+    /// A fixed number of bins
+    Count(u32),
+    /// The square root of the number of values
+    Sqrt,
+    /// Sturges' formula, the default
+    Sturges,
+    /// The Rice rule
+    Rice,
+    /// Scott's normal reference rule, based on the standard deviation of the values
+    Scott,
+}
+
+impl HistogramBins {
+    /// The bins argument ImPlot expects, which is the count or a negative value for the methods.
+    fn to_i32(self) -> i32 {
+        match self {
+            HistogramBins::Count(count) => i32::try_from(count.max(1)).unwrap_or(i32::MAX),
+            HistogramBins::Sqrt => sys::ImPlotBin__ImPlotBin_Sqrt,
+            HistogramBins::Sturges => sys::ImPlotBin__ImPlotBin_Sturges,
+            HistogramBins::Rice => sys::ImPlotBin__ImPlotBin_Rice,
+            HistogramBins::Scott => sys::ImPlotBin__ImPlotBin_Scott,
+        }
+    }
+}
+
+/// Struct to provide functionality for plotting histograms of raw sample data. ImPlot sorts the
+/// values into bins and draws the counts as bars.
+pub struct PlotHistogram {
+    /// Label to show in the legend for this histogram
+    label: CString,
+    /// How the number of bins is chosen
+    bins: HistogramBins,
+    /// Width of the bars, as a fraction of the bin width
+    bar_scale: f64,
+    /// Range of values that is split into bins. If this is `None`, the range of the data is used.
+    range: Option<(f64, f64)>,
+    flags: HistogramFlags,
+}
+
+impl PlotHistogram {
+    /// Create a new histogram to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self::new_with_flags(label, HistogramFlags::empty())
+    }
+
+    /// Create a new histogram to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new_with_flags(label: &str, flags: HistogramFlags) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            // Default values taken from C++ implot
+            bins: HistogramBins::Sturges,
+            bar_scale: 1.0,
+            range: None,
+            flags,
+        }
+    }
+
+    pub fn flags(mut self, flags: HistogramFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Set how the number of bins is chosen. Defaults to [`HistogramBins::Sturges`].
+    pub fn with_bins(mut self, bins: HistogramBins) -> Self {
+        self.bins = bins;
+        self
+    }
+
+    /// Set the width of the bars as a fraction of the bin width. Defaults to 1, where the bars
+    /// touch each other.
+    pub fn with_bar_scale(mut self, bar_scale: f64) -> Self {
+        self.bar_scale = bar_scale;
+        self
+    }
+
+    /// Set the range of values from `range.0` to `range.1` that is split into bins, for example
+    /// to keep the bins the same while the data changes. Values outside of it are not counted in
+    /// any bin; with `HistogramFlags::NO_OUTLIERS`, they are also left out when normalizing and
+    /// accumulating. Defaults to the range of the data.
+    pub fn with_range(mut self, range: Option<(f64, f64)>) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Draw the histogram of the given values, and return the height of its highest bar, which
+    /// is a count or, with `HistogramFlags::DENSITY`, a density. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build). Returns zero if there are no values.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotHistogram"))]
+    pub fn plot(&self, values: &[f64]) -> f64 {
+        // If there is no data to plot, we stop here
+        if values.is_empty() {
+            return 0.0;
+        }
+        // ImPlot bins the values itself, so the extents of the bars are not known here
+        let (min, max) = self.range.unwrap_or((0.0, 0.0)); // An empty range means automatic
+        let max_bin_height = unsafe {
+            sys::ImPlot_PlotHistogram_doublePtr(
+                crate::items::legend_label(&self.label).as_ptr(),
+                values.as_ptr(),
+                values.len() as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                self.bins.to_i32(),
+                self.bar_scale,
+                ImPlotRange { Min: min, Max: max },
+                self.flags.bits() as sys::ImPlotHistogramFlags_,
+            )
+        };
+        crate::items::record_last_item(&self.label, "PlotHistogram", values.len());
+        max_bin_height
+    }
+}
+
+/// Struct to provide functionality for plotting two-dimensional histograms, for example to show
+/// the density of scatter data with too many points to tell apart. ImPlot counts the points in
+/// a grid of bins and draws the counts as a heatmap, colored with the current colormap. This
+/// pairs well with a colormap pushed with [`crate::push_colormap_from_preset`] for the plot and
+/// a [`crate::colormap_scale`] next to it that shows the counts.
+pub struct PlotHistogram2D {
+    /// Label to show in the legend for this histogram
+    label: CString,
+    /// How the number of bins along the X axis is chosen
+    x_bins: HistogramBins,
+    /// How the number of bins along the Y axis is chosen
+    y_bins: HistogramBins,
+    /// Range of X values that is split into bins. If this is `None`, the range of the data is used.
+    x_range: Option<(f64, f64)>,
+    /// Range of Y values that is split into bins. If this is `None`, the range of the data is used.
+    y_range: Option<(f64, f64)>,
+    flags: HistogramFlags,
+}
+
+impl PlotHistogram2D {
+    /// Create a new two-dimensional histogram to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self::new_with_flags(label, HistogramFlags::empty())
+    }
+
+    /// Create a new two-dimensional histogram to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new_with_flags(label: &str, flags: HistogramFlags) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            // Default values taken from C++ implot
+            x_bins: HistogramBins::Sturges,
+            y_bins: HistogramBins::Sturges,
+            x_range: None,
+            y_range: None,
+            flags,
+        }
+    }
+
+    /// Set the flags. Of the histogram flags, `DENSITY`, `NO_OUTLIERS` and `COL_MAJOR` apply to
+    /// two-dimensional histograms.
+    pub fn flags(mut self, flags: HistogramFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Set how the number of bins along the X axis is chosen. Defaults to
+    /// [`HistogramBins::Sturges`].
+    pub fn with_x_bins(mut self, bins: HistogramBins) -> Self {
+        self.x_bins = bins;
+        self
+    }
+
+    /// Set how the number of bins along the Y axis is chosen. Defaults to
+    /// [`HistogramBins::Sturges`].
+    pub fn with_y_bins(mut self, bins: HistogramBins) -> Self {
+        self.y_bins = bins;
+        self
+    }
+
+    /// Set the range of X values that is split into bins, see [`PlotHistogram::with_range`].
+    /// Defaults to the range of the data.
+    pub fn with_x_range(mut self, range: Option<(f64, f64)>) -> Self {
+        self.x_range = range;
+        self
+    }
+
+    /// Set the range of Y values that is split into bins, see [`PlotHistogram::with_range`].
+    /// Defaults to the range of the data.
+    pub fn with_y_range(mut self, range: Option<(f64, f64)>) -> Self {
+        self.y_range = range;
+        self
+    }
+
+    /// Draw the histogram of the points given by `x` and `y`, and return the count of its
+    /// fullest bin, or its density with `HistogramFlags::DENSITY`. This is the upper end of the
+    /// colormap scale. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build). Returns zero if there are no points.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotHistogram2D"))]
+    pub fn plot(&self, x: &[f64], y: &[f64]) -> f64 {
+        let number_of_points = x.len().min(y.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return 0.0;
+        }
+        crate::extents::track_points(x, y);
+        // An empty range means automatic
+        let (x_min, x_max) = self.x_range.unwrap_or((0.0, 0.0));
+        let (y_min, y_max) = self.y_range.unwrap_or((0.0, 0.0));
+        let max_bin_height = unsafe {
+            sys::ImPlot_PlotHistogram2D_doublePtr(
+                crate::items::legend_label(&self.label).as_ptr(),
+                x.as_ptr(),
+                y.as_ptr(),
+                number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                self.x_bins.to_i32(),
+                self.y_bins.to_i32(),
+                ImPlotRect {
+                    X: ImPlotRange {
+                        Min: x_min,
+                        Max: x_max,
+                    },
+                    Y: ImPlotRange {
+                        Min: y_min,
+                        Max: y_max,
+                    },
+                },
+                self.flags.bits() as sys::ImPlotHistogramFlags_,
+            )
+        };
+        crate::items::record_last_item(&self.label, "PlotHistogram2D", number_of_points);
+        max_bin_height
+    }
+}
+
+/// Struct to provide functionality for adding a legend entry that does not plot any data, for
+/// example to stand for a group of things drawn by hand onto the plot. The entry gets the next
+/// color of the colormap like other items, unless `PlotColorElement::Line` is pushed with
+/// [`crate::push_style_color`]. For an entry with a given color in one call, see
+/// [`crate::legend_entry_only`].
+///
+/// Queries by label such as [`crate::is_legend_entry_hovered`] work with the entry. ImPlot does
+/// not expose whether the user toggled an entry off by clicking it though, so drawing that
+/// should follow the entry has to be toggled separately.
+pub struct PlotDummy {
+    /// Label to show in the legend for this entry
+    label: CString,
+    flags: DummyFlags,
+}
+
+impl PlotDummy {
+    /// Create a new legend entry to be plotted. Does not add anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self::new_with_flags(label, DummyFlags::empty())
+    }
+
+    /// Create a new legend entry to be plotted. Does not add anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new_with_flags(label: &str, flags: DummyFlags) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            flags,
+        }
+    }
+
+    pub fn flags(mut self, flags: DummyFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Add the legend entry. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build).
+    #[rustversion::attr(since(1.48), doc(alias = "PlotDummy"))]
+    pub fn plot(&self) {
+        unsafe {
+            sys::ImPlot_PlotDummy(
+                crate::items::legend_label(&self.label).as_ptr(),
+                self.flags.bits() as sys::ImPlotDummyFlags_,
+            );
+        }
+        crate::items::record_last_item(&self.label, "PlotDummy", 0);
+    }
+}
+
+/// Struct to provide stem plotting functionality.
+pub struct PlotStems {
+    /// Label to show in the legend for this line
+    label: CString,
+
+    /// Reference value for the y value, which the stems are "with respect to"
+    reference_y: f64,
+    flags: StemsFlags,
+
+    /// Stride and offset of the data passed to [`PlotStems::plot`]
+    layout: DataLayout,
+}
+
+impl PlotStems {
+    /// Create a new stem plot to be shown. Does not draw anything by itself, call
+    /// [`PlotStems::plot`] on the struct for that.
+    pub fn new(label: &str) -> Self {
+        Self::new_with_flags(label, StemsFlags::empty())
+    }
+
+    pub fn new_with_flags(label: &str, flags: StemsFlags) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            reference_y: 0.0, // Default value taken from C++ implot
+            flags,
+            layout: DataLayout::default(),
+        }
+    }
+
+    /// Set the reference y value for the stems
+    pub fn with_reference_y(mut self, reference_y: f64) -> Self {
+        self.reference_y = reference_y;
+        self
+    }
+
+    /// Set the distance between consecutive values of the data in **bytes**, see
+    /// [`PlotLine::with_stride`].
+    pub fn with_stride(mut self, bytes: i32) -> Self {
+        self.layout.stride = Some(bytes);
+        self
+    }
+
+    /// Set the index of the value that is plotted first, counted in **values**, see
+    /// [`PlotLine::with_offset`].
+    pub fn with_offset(mut self, count: i32) -> Self {
+        self.layout.offset = count;
+        self
+    }
+
+    /// Draw a previously-created stem plot. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build). The `axis_positions` specify where on the
+    /// X axis the stems are drawn, and the `stem_values` specify what values the stems have.
+    /// The data can be of any type implementing [`ImPlotData`].
+    pub fn plot<T: ImPlotData>(&self, axis_positions: &[T], stem_values: &[T]) {
+        let number_of_points = self.layout.point_count(axis_positions, stem_values);
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+        self.layout
+            .track_points(axis_positions, stem_values, number_of_points);
+        unsafe {
+            T::PLOT_STEMS(
+                crate::items::legend_label(&self.label).as_ptr(),
+                axis_positions.as_ptr(),
+                stem_values.as_ptr(),
+                number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                self.reference_y,
+                self.flags.bits() as sys::ImPlotStemsFlags_,
+                self.layout.offset,
+                self.layout.stride_bytes::<T>(),
+            );
+        }
+        crate::items::record_last_item(&self.label, "PlotStems", number_of_points);
+    }
+}
+
+/// Struct to provide functionality for plotting a mean line together with a shaded confidence
+/// band of `mean ± k * std` around it. Both parts share one legend entry and one color.
+pub struct PlotConfidenceBand {
+    /// Label to show in the legend for the line and its band
+    label: CString,
+
+    /// Alpha modifier for the fill of the band, so the line stays visible on top of it
+    fill_alpha: f32,
+}
+
+impl PlotConfidenceBand {
+    /// Create a new confidence band plot to be shown. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            fill_alpha: 0.25,
+        }
+    }
+
+    /// Set the alpha modifier applied to the fill color of the band. Defaults to 0.25.
+    pub fn with_fill_alpha(mut self, fill_alpha: f32) -> Self {
+        self.fill_alpha = fill_alpha;
+        self
+    }
+
+    /// Draw the mean line and the band between `mean - k * std` and `mean + k * std`. Use this
+    /// in closures passed to [`Plot::build()`](struct.Plot.html#method.build).
+    pub fn plot(&self, x: &[f64], mean: &[f64], std: &[f64], k: f64) {
+        let number_of_points = x.len().min(mean.len()).min(std.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+
+        let (mut lower, mut upper) = (ScratchBuffer::new(), ScratchBuffer::new());
+        for (m, s) in mean[..number_of_points].iter().zip(std.iter()) {
+            lower.push(m - k * s);
+            upper.push(m + k * s);
+        }
+        self.plot_band(
+            &x[..number_of_points],
+            &lower,
+            &mean[..number_of_points],
+            &upper,
+        );
+    }
+
+    /// Draw the median line `p_mid` and the band between the percentiles `p_low` and `p_high`,
+    /// for example the 5th, 50th and 95th percentiles of Monte Carlo results. Like for
+    /// [`PlotConfidenceBand::plot`], the line and the band share one legend entry. Use this in
+    /// closures passed to [`Plot::build()`](struct.Plot.html#method.build).
+    pub fn plot_percentile_band(&self, x: &[f64], p_low: &[f64], p_mid: &[f64], p_high: &[f64]) {
+        let number_of_points = x.len().min(p_low.len()).min(p_mid.len()).min(p_high.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+
+        self.plot_band(
+            &x[..number_of_points],
+            &p_low[..number_of_points],
+            &p_mid[..number_of_points],
+            &p_high[..number_of_points],
+        );
+    }
+
+    /// Internal helper to draw the band between `lower` and `upper` and the line `center` on top
+    /// of it, all of which have the same length as `x`.
+    fn plot_band(&self, x: &[f64], lower: &[f64], center: &[f64], upper: &[f64]) {
+        let number_of_points = x.len();
+        crate::extents::track_points(x, lower);
+        crate::extents::track_points(x, upper);
+        unsafe {
+            // Items with the same label share their legend entry and color in ImPlot
+            sys::ImPlot_SetNextFillStyle(
+                sys::ImVec4 { x: 0.0, y: 0.0, z: 0.0, w: -1.0 }, // IMPLOT_AUTO_COL
+                self.fill_alpha,
+            );
+            sys::ImPlot_PlotShaded_doublePtrdoublePtrdoublePtr(
+                crate::items::legend_label(&self.label).as_ptr(),
+                x.as_ptr(),
+                lower.as_ptr(),
+                upper.as_ptr(),
+                number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                0,                                 // No flags
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+            sys::ImPlot_PlotLine_doublePtrdoublePtr(
+                crate::items::legend_label(&self.label).as_ptr(),
+                x.as_ptr(),
+                center.as_ptr(),
+                number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                0,                                 // No flags
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+        crate::items::record_last_item(&self.label, "PlotConfidenceBand", number_of_points);
+    }
+}
+
+/// Struct to provide functionality for a line whose points the user can drag around, with the
+/// changes written back to the data.
+pub struct EditablePlotLine {
+    /// The line itself
+    line: PlotLine,
+    /// Drag point ID of the first point, the others follow consecutively
+    first_id: i32,
+    /// Maximum number of points that get a drag point
+    max_points: usize,
+    /// Radius of the drag points, in pixels
+    point_size: f32,
+}
+
+impl EditablePlotLine {
+    /// Create a new editable line to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            line: PlotLine::new(label),
+            first_id: 0,
+            max_points: 1000,
+            point_size: 4.0,
+        }
+    }
+
+    /// Set the drag point ID of the first point. The points use the consecutive IDs
+    /// `first_id..first_id + max_points`, which must not overlap with the IDs of other drag tools
+    /// in the same plot, so set this when using more than one editable line per plot.
+    pub fn with_first_id(mut self, first_id: i32) -> Self {
+        self.first_id = first_id;
+        self
+    }
+
+    /// Set the maximum number of points that can be dragged, 1000 by default. Points past this
+    /// number are still drawn as part of the line, but cannot be dragged. This bounds the range
+    /// of drag point IDs used, see [`EditablePlotLine::with_first_id`].
+    pub fn with_max_points(mut self, max_points: usize) -> Self {
+        self.max_points = max_points;
+        self
+    }
+
+    /// Set the radius of the drag points, in pixels.
+    pub fn with_point_size(mut self, point_size: f32) -> Self {
+        self.point_size = point_size;
+        self
+    }
+
+    /// Plot the line and a drag point on each of its points, and write the positions of dragged
+    /// points back into `x` and `y`. Returns whether any point was moved. Use this in closures
+    /// passed to [`Plot::build()`](struct.Plot.html#method.build)
+    ///
+    /// Every drag point is a separate interactive element, so this gets slow for lines with many
+    /// thousands of points. Keep the number of editable points to what can be edited sensibly by
+    /// hand, for example by editing a coarse set of control points.
+    pub fn plot(&self, x: &mut [f64], y: &mut [f64]) -> bool {
+        let number_of_points = x.len().min(y.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return false;
+        }
+
+        self.line.plot(x, y);
+        let mut color = ImVec4 { x: 0.0, y: 0.0, z: 0.0, w: 0.0 };
+        unsafe { sys::ImPlot_GetLastItemColor(&mut color as *mut ImVec4) };
+
+        let mut changed = false;
+        for (i, (x, y)) in x
+            .iter_mut()
+            .zip(y.iter_mut())
+            .take(self.max_points)
+            .enumerate()
+        {
+            let id = self.first_id.saturating_add(i as i32);
+            changed |= drag_point(id, x, y, color, self.point_size, DragToolFlags::NONE);
+        }
+        changed
+    }
+}
+
+/// Struct to provide functionality for a horizontal threshold line the user can drag, which
+/// finds and marks the points where a data series crosses it.
+pub struct ThresholdTool {
+    /// Label of the markers drawn at the crossings
+    label: CString,
+    /// Drag tool ID of the threshold line
+    id: i32,
+    /// Color of the threshold line and the markers
+    color: ImVec4,
+    /// Width of the threshold line, in pixels
+    thickness: f32,
+    /// Marker drawn at the crossings
+    marker: Marker,
+    /// Size of the markers, in pixels
+    marker_size: f32,
+}
+
+impl ThresholdTool {
+    /// Create a new threshold tool, whose crossing markers show up in the legend under the given
+    /// label. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            id: 0,
+            color: ImVec4 { x: 1.0, y: 0.0, z: 0.0, w: 1.0 },
+            thickness: 1.0,
+            marker: Marker::Circle,
+            marker_size: 4.0,
+        }
+    }
+
+    /// Set the drag tool ID of the threshold line, which must not be used by other drag tools in
+    /// the same plot. 0 by default.
+    pub fn with_id(mut self, id: i32) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Set the color of the threshold line and of the crossing markers, red by default.
+    pub fn with_color(mut self, color: ImVec4) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the width of the threshold line, in pixels.
+    pub fn with_thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Set the marker drawn at the crossings and its size in pixels. Use [`Marker::None`] to
+    /// only compute the crossings without marking them.
+    pub fn with_marker(mut self, marker: Marker, size: f32) -> Self {
+        self.marker = marker;
+        self.marker_size = size;
+        self
+    }
+
+    /// Get the indices at which the series `y` crosses `threshold`. An index `i` is included if
+    /// `y[i - 1]` and `y[i]` are on different sides of the threshold, where values equal to the
+    /// threshold count as above it. Pairs involving non-finite values are skipped.
+    pub fn crossings(y: &[f64], threshold: f64) -> Vec<usize> {
+        (1..y.len())
+            .filter(|&i| {
+                let (previous, current) = (y[i - 1], y[i]);
+                previous.is_finite()
+                    && current.is_finite()
+                    && (previous < threshold) != (current < threshold)
+            })
+            .collect()
+    }
+
+    /// Draw the threshold line at `threshold`, which is updated while the user drags it, and
+    /// markers where the series given by `x` and `y` crosses it, placed by linear interpolation
+    /// between the neighboring points. Returns the crossing indices as computed by
+    /// [`ThresholdTool::crossings`]. Plot the series itself separately. Use this in closures
+    /// passed to [`Plot::build()`](struct.Plot.html#method.build)
+    pub fn plot(&self, threshold: &mut f64, x: &[f64], y: &[f64]) -> Vec<usize> {
+        drag_line_y(
+            self.id,
+            threshold,
+            self.color,
+            self.thickness,
+            DragToolFlags::NONE,
+        );
+
+        let number_of_points = x.len().min(y.len());
+        let crossings = Self::crossings(&y[..number_of_points], *threshold);
+        if crossings.is_empty() || matches!(self.marker, Marker::None) {
+            return crossings;
+        }
+
+        let marker_x: ScratchBuffer = crossings
+            .iter()
+            .map(|&i| {
+                let t = (*threshold - y[i - 1]) / (y[i] - y[i - 1]);
+                x[i - 1] + t * (x[i] - x[i - 1])
+            })
+            .collect();
+        let marker_y: ScratchBuffer = crossings.iter().map(|_| *threshold).collect();
+        crate::extents::track_points(&marker_x, &marker_y);
+        unsafe {
+            sys::ImPlot_SetNextMarkerStyle(
+                self.marker as sys::ImPlotMarker,
+                self.marker_size,
+                self.color,
+                IMPLOT_AUTO as f32,
+                self.color,
+            );
+            sys::ImPlot_PlotScatter_doublePtrdoublePtr(
+                crate::items::legend_label(&self.label).as_ptr(),
+                marker_x.as_ptr(),
+                marker_y.as_ptr(),
+                marker_x.len() as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                0,                     // No flags
+                0,                     // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+        crate::items::record_last_item(&self.label, "ThresholdTool", marker_x.len());
+        crossings
+    }
+}
+
+/// Plot two lines `a` and `b` over the same X values, with the area between them filled in
+/// `above_color` where `a` is above `b` and in `below_color` where it is below, as in an "actual
+/// vs. budget" chart. The fill switches color exactly where the lines cross, which is found by
+/// linear interpolation between the points. The lines are labeled `a_label` and `b_label` and
+/// colored like other items, while the fill has no legend entry of its own. Pass colors with an
+/// alpha below one to keep the grid visible through the fill. Use this in closures passed to
+/// [`Plot::build()`](struct.Plot.html#method.build)
+///
+/// # Panics
+/// Will panic if a label string contains internal null bytes.
+pub fn plot_comparison(
+    x: &[f64],
+    a: &[f64],
+    b: &[f64],
+    a_label: &str,
+    b_label: &str,
+    above_color: ImVec4,
+    below_color: ImVec4,
+) {
+    let number_of_points = x.len().min(a.len()).min(b.len());
+    // If there is no data to plot, we stop here
+    if number_of_points == 0 {
+        return;
+    }
+    let x = &x[..number_of_points];
+    let a = &a[..number_of_points];
+    let b = &b[..number_of_points];
+
+    // The points of both lines, plus the points where they cross, so the fills of both colors
+    // meet there
+    let mut fill_x = ScratchBuffer::new();
+    let mut fill_a = ScratchBuffer::new();
+    let mut fill_b = ScratchBuffer::new();
+    for i in 0..number_of_points {
+        if i > 0 {
+            let (previous, current) = (a[i - 1] - b[i - 1], a[i] - b[i]);
+            if previous * current < 0.0 {
+                let t = previous / (previous - current);
+                let crossing = a[i - 1] + t * (a[i] - a[i - 1]);
+                fill_x.push(x[i - 1] + t * (x[i] - x[i - 1]));
+                fill_a.push(crossing);
+                fill_b.push(crossing);
+            }
+        }
+        fill_x.push(x[i]);
+        fill_a.push(a[i]);
+        fill_b.push(b[i]);
+    }
+    // Each fill spans from `b` to `a` where its sign applies, and has no height elsewhere
+    let above: ScratchBuffer = fill_a
+        .iter()
+        .zip(fill_b.iter())
+        .map(|(a, b)| a.max(*b))
+        .collect();
+    let below: ScratchBuffer = fill_a
+        .iter()
+        .zip(fill_b.iter())
+        .map(|(a, b)| a.min(*b))
+        .collect();
+
+    let fill_label = CString::new(format!("##comparison fill {} {}", a_label, b_label))
+        .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", a_label));
+    let fill_flags = sys::ImPlotItemFlags__ImPlotItemFlags_NoLegend;
+    for (color, lower, upper, suffix) in [
+        (above_color, &fill_b, &above, " above"),
+        (below_color, &below, &fill_b, " below"),
+    ]
+    .iter()
+    {
+        let label = with_label_suffix(&fill_label, suffix);
+        unsafe {
+            sys::ImPlot_SetNextFillStyle(*color, 1.0);
+            sys::ImPlot_PlotShaded_doublePtrdoublePtrdoublePtr(
+                label.as_ptr(),
+                fill_x.as_ptr(),
+                lower.as_ptr(),
+                upper.as_ptr(),
+                fill_x.len() as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                fill_flags,
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+    }
+
+    PlotLine::new(a_label).plot(x, a);
+    PlotLine::new(b_label).plot(x, b);
+}
+
+// --- Hit testing -------------------------------------------------------------------------------
+/// Trait for plot elements that can tell which of their data points is closest to the mouse,
+/// for example to show tooltips or to select points. This works in pixels, so the result is
+/// consistent regardless of zoom and axis scales.
+pub trait Hoverable {
+    /// Get the index of the data point nearest to the mouse cursor, out of the data the element
+    /// was plotted with, or `None` if no point is within `threshold_px` pixels of the cursor.
+    /// `x` and `y` have to be the same slices that were passed to `plot`. Call this in the
+    /// closure passed to [`Plot::build()`](struct.Plot.html#method.build), with the same axes
+    /// selected as for plotting. Combine it with `is_plot_hovered` to ignore the mouse while it
+    /// is outside of the plot.
+    fn nearest(&self, x: &[f64], y: &[f64], threshold_px: f32) -> Option<usize>;
+}
+
+/// Get the current mouse position in pixels.
+fn mouse_pixel_position() -> ImVec2 {
+    let mut position = ImVec2 { x: 0.0, y: 0.0 };
+    unsafe { sys::igGetMousePos(&mut position as *mut ImVec2) };
+    position
+}
+
+/// Internal helper to find the index with the smallest pixel distance, as computed by the given
+/// function, among the first `number_of_points` indices, if it is within the threshold.
+fn nearest_index<F: Fn(usize) -> f32>(
+    number_of_points: usize,
+    threshold_px: f32,
+    distance: F,
+) -> Option<usize> {
+    (0..number_of_points)
+        .map(|i| (i, distance(i)))
+        .filter(|(_, d)| *d <= threshold_px)
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(i, _)| i)
+}
+
+/// Pixel distance from `point` to the axis-aligned rectangle spanned by the two given corners,
+/// which is zero inside the rectangle.
+fn distance_to_rect(point: ImVec2, corner_a: ImVec2, corner_b: ImVec2) -> f32 {
+    let dx = (corner_a.x.min(corner_b.x) - point.x).max(point.x - corner_a.x.max(corner_b.x));
+    let dy = (corner_a.y.min(corner_b.y) - point.y).max(point.y - corner_a.y.max(corner_b.y));
+    let (dx, dy) = (dx.max(0.0), dy.max(0.0));
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// Pixel distance from the mouse to each point, used by the elements drawing one mark per point.
+fn nearest_point(x: &[f64], y: &[f64], threshold_px: f32) -> Option<usize> {
+    let mouse = mouse_pixel_position();
+    nearest_index(x.len().min(y.len()), threshold_px, |i| {
+        let position = to_pixels(x[i], y[i]);
+        distance_to_rect(mouse, position, position)
+    })
+}
+
+impl Hoverable for PlotLine {
+    fn nearest(&self, x: &[f64], y: &[f64], threshold_px: f32) -> Option<usize> {
+        nearest_point(x, y, threshold_px)
+    }
+}
+
+impl Hoverable for PlotScatter {
+    fn nearest(&self, x: &[f64], y: &[f64], threshold_px: f32) -> Option<usize> {
+        nearest_point(x, y, threshold_px)
+    }
+}
+
+impl Hoverable for PlotStems {
+    /// The distance to a stem is measured to its whole line down to the reference value, so
+    /// `x` are the `axis_positions` and `y` the `stem_values` the stems were plotted with.
+    fn nearest(&self, x: &[f64], y: &[f64], threshold_px: f32) -> Option<usize> {
+        let mouse = mouse_pixel_position();
+        nearest_index(x.len().min(y.len()), threshold_px, |i| {
+            distance_to_rect(mouse, to_pixels(x[i], self.reference_y), to_pixels(x[i], y[i]))
+        })
+    }
+}
+
+impl Hoverable for PlotBars {
+    /// The distance to a bar is measured to its whole body, so hovering anywhere on a bar selects
+    /// it. `x` are the `axis_positions` and `y` the `bar_values` the bars were plotted with,
+    /// regardless of whether the bars are horizontal.
+    fn nearest(&self, x: &[f64], y: &[f64], threshold_px: f32) -> Option<usize> {
+        let mouse = mouse_pixel_position();
+        let half_width = self.bar_width_for(x, x.len().min(y.len()), DataLayout::default()) / 2.0;
+        let horizontal = self.flags.contains(BarsFlags::HORIZONTAL);
+        nearest_index(x.len().min(y.len()), threshold_px, |i| {
+            let (start, end) = (x[i] - half_width, x[i] + half_width);
+            if horizontal {
+                distance_to_rect(mouse, to_pixels(0.0, start), to_pixels(y[i], end))
+            } else {
+                distance_to_rect(mouse, to_pixels(start, 0.0), to_pixels(end, y[i]))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The fields are only read through a flat view of the vertices
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct Vertex {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+
+    #[test]
+    fn strided_f32_vertices() {
+        let vertices = [
+            Vertex { x: 1.0, y: 2.0, z: 3.0 },
+            Vertex { x: 4.0, y: 5.0, z: 6.0 },
+            Vertex { x: 7.0, y: 8.0, z: 9.0 },
+        ];
+        // The vertices are three tightly packed f32 each, so they can be viewed as a flat slice
+        let data = unsafe {
+            std::slice::from_raw_parts(vertices.as_ptr() as *const f32, vertices.len() * 3)
+        };
+        let stride = std::mem::size_of::<Vertex>();
+        assert_eq!(stride, 12);
+
+        assert_eq!(check_strided_layout(data, 3, 0, 4, stride), 12);
+        assert_eq!(*strided_values(data, 3, 0, stride), vec![1.0, 4.0, 7.0]);
+        assert_eq!(*strided_values(data, 3, 4, stride), vec![2.0, 5.0, 8.0]);
+        // Y and Z of each vertex, starting at the second one
+        assert_eq!(check_strided_layout(data, 2, 16, 20, stride), 12);
+        assert_eq!(*strided_values(data, 2, 20, stride), vec![6.0, 9.0]);
+    }
+
+    #[test]
+    fn generic_plot_data() {
+        // Empty data returns before anything is plotted, so this only needs the types to work
+        PlotLine::new("f32").plot::<f32>(&[], &[]);
+        PlotLine::new("i32").plot::<i32>(&[], &[]);
+        PlotScatter::new("f32").plot::<f32>(&[], &[]);
+        PlotScatter::new("i32").plot::<i32>(&[], &[]);
+        PlotBars::new("f32").plot::<f32>(&[], &[]);
+        PlotBars::new("i32").plot::<i32>(&[], &[]);
+        PlotStems::new("f32").plot::<f32>(&[], &[]);
+        PlotStems::new("i32").plot::<i32>(&[], &[]);
+
+        assert_eq!(*f64_values(&[1.5f32, -2.0]), vec![1.5, -2.0]);
+        assert_eq!(*f64_values(&[-3i32, 4]), vec![-3.0, 4.0]);
+        assert_eq!(*f64_values(&[u32::MAX]), vec![4294967295.0]);
+        assert_eq!(*f64_values(&[7u8, 255]), vec![7.0, 255.0]);
+        assert_eq!(f64::as_f64_slice(&[1.0, 2.0]), Some(&[1.0, 2.0][..]));
+        assert_eq!(f32::as_f64_slice(&[1.0, 2.0]), None);
+    }
+
+    #[test]
+    fn data_layouts() {
+        let plain = DataLayout::default();
+        assert_eq!(plain.point_count(&[1.0, 2.0, 3.0], &[4.0, 5.0]), 2);
+        assert_eq!(plain.stride_bytes::<f32>(), 4);
+
+        // Points of two f64 fields, seen from the first and from the second field
+        let fields = [0.0, 10.0, 1.0, 11.0, 2.0, 12.0];
+        let strided = DataLayout {
+            stride: Some(16),
+            offset: 1,
+        };
+        assert_eq!(strided.point_count(&fields[0..], &fields[1..]), 3);
+        assert_eq!(strided.stride_bytes::<f64>(), 16);
+        assert_eq!(*strided.values(&fields[0..], 3), vec![1.0, 2.0, 0.0]);
+        assert_eq!(*strided.values(&fields[1..], 3), vec![11.0, 12.0, 10.0]);
+
+        let from_end = DataLayout {
+            stride: None,
+            offset: -1,
+        };
+        assert_eq!(*from_end.values(&[1i32, 2, 3], 3), vec![3.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a positive multiple")]
+    fn data_layout_stride_between_values() {
+        let layout = DataLayout {
+            stride: Some(6),
+            offset: 0,
+        };
+        layout.point_count(&[0.0f32; 4], &[0.0f32; 4]);
+    }
+
+    #[test]
+    fn moving_average_skips_non_finite_values() {
+        let values = [1.0, 2.0, f64::NAN, 4.0, 5.0, 6.0, f64::INFINITY];
+        let averages = centered_moving_average(&values, 3);
+        assert_eq!(averages[0], 1.5);
+        assert_eq!(averages[1], 1.5);
+        assert_eq!(averages[2], 3.0);
+        assert_eq!(averages[3], 4.5);
+        // The window no longer contains the NaN, so it does not affect the average
+        assert_eq!(averages[4], 5.0);
+        assert_eq!(averages[5], 5.5);
+        assert_eq!(averages[6], 6.0);
+        assert!(centered_moving_average(&[f64::NAN, f64::NAN], 2)[0].is_nan());
+    }
+
+    #[test]
+    #[should_panic(expected = "is not positive")]
+    fn non_positive_clamp_floor() {
+        PlotLine::new("log").with_non_positive_values(NonPositiveValues::Clamp(0.0));
+    }
+
+    #[test]
+    fn bar_position_spacing() {
+        assert_eq!(minimum_spacing(&[0.0, 1.0, 2.0, 3.0]), Some(1.0));
+        assert_eq!(minimum_spacing(&[4.0, 0.0, 2.5, 1.0]), Some(1.0));
+        assert_eq!(minimum_spacing(&[1.0, 1.0, 1.5, f64::NAN]), Some(0.5));
+        assert_eq!(minimum_spacing(&[2.0, 2.0]), None);
+        assert_eq!(minimum_spacing(&[]), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Strided data needs")]
+    fn strided_data_too_short() {
+        let data = [0.0f32; 7];
+        check_strided_layout(&data, 3, 0, 4, 12);
     }
 }
@@ -0,0 +1,186 @@
+//! # Plot state module
+//!
+//! This module keeps the state plots carry over from one frame to the next, per plot title.
+//! Some of what a plot is set up with depends on what happened during the previous frame it was
+//! shown on: the limits of its axes, the size of its plot area, the category labels plotted into
+//! it, and whether it was shown at all. Plots store these in a [`PlotState`] when they end, and
+//! read them back when they are begun again. The plot the user interacted with most recently is
+//! kept here as well, and so is the frame rectangle of the plot that is being built, which
+//! ImPlot does not expose.
+use crate::{sys, Axis, ImPlotRange, ImPlotRect, ImVec2};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    ffi::CString,
+};
+
+/// What a plot stored about the most recent frame it was shown on.
+#[derive(Default)]
+struct PlotState {
+    /// Limits of the first X axis
+    x_limits: Option<ImPlotRange>,
+    /// Limits of the first X and Y axes and size of the plot area in pixels
+    view: Option<(ImPlotRect, ImVec2)>,
+    /// Limits of axes with animated limits and the frame they were stored on
+    axis_limits: Option<(i32, Vec<(Axis, ImPlotRange)>)>,
+    /// Category labels plotted during the frame, to set up as ticks on the next frame
+    category_ticks: Vec<(Axis, Vec<CString>)>,
+    /// ImGui frame count of the most recent frame the plot was begun on
+    last_shown_frame: Option<i32>,
+}
+
+thread_local! {
+    /// State of each plot, per plot title. ImGui and ImPlot are single-threaded, so a thread
+    /// local matches their model.
+    static PLOT_STATES: RefCell<HashMap<CString, PlotState>> = RefCell::new(HashMap::new());
+
+    /// Title of the plot the user interacted with most recently
+    static ACTIVE_TITLE: RefCell<Option<CString>> = const { RefCell::new(None) };
+
+    /// Title of the plot that was begun most recently
+    static CURRENT_TITLE: RefCell<Option<CString>> = const { RefCell::new(None) };
+
+    /// Corners of the frame of the plot that was begun most recently
+    static CURRENT_FRAME: Cell<(ImVec2, ImVec2)> =
+        const { Cell::new((ImVec2 { x: 0.0, y: 0.0 }, ImVec2 { x: 0.0, y: 0.0 })) };
+}
+
+/// Run the given function on the state of the plot with the given title, creating it if needed.
+fn with_state<R>(title: &CString, f: impl FnOnce(&mut PlotState) -> R) -> R {
+    PLOT_STATES.with(|states| {
+        let mut states = states.borrow_mut();
+        f(states
+            .entry(title.clone())
+            .or_insert_with(PlotState::default))
+    })
+}
+
+/// Run the given function on the state of the plot with the given title, if there is any.
+fn read_state<R>(title: &CString, f: impl FnOnce(&PlotState) -> Option<R>) -> Option<R> {
+    PLOT_STATES.with(|states| states.borrow().get(title).and_then(f))
+}
+
+/// Store the limits the first X axis of the plot with the given title had on this frame.
+pub(crate) fn store_x_limits(title: &CString, limits: ImPlotRange) {
+    with_state(title, |state| state.x_limits = Some(limits));
+}
+
+/// Get the limits the first X axis of the plot with the given title had on the most recent frame
+/// they were stored.
+pub(crate) fn previous_x_limits(title: &CString) -> Option<ImPlotRange> {
+    read_state(title, |state| state.x_limits)
+}
+
+/// Store the limits the given axes of the plot with the given title had on this frame.
+pub(crate) fn store_axis_limits(title: &CString, limits: Vec<(Axis, ImPlotRange)>) {
+    let frame = unsafe { sys::igGetFrameCount() };
+    with_state(title, |state| state.axis_limits = Some((frame, limits)));
+}
+
+/// Get the limits the given axis of the plot with the given title had on the previous frame, if
+/// they were stored on that frame. Limits stored longer ago may no longer be accurate.
+pub(crate) fn previous_frame_axis_limits(title: &CString, axis: Axis) -> Option<ImPlotRange> {
+    let frame = unsafe { sys::igGetFrameCount() };
+    read_state(title, |state| {
+        let (stored_frame, limits) = state.axis_limits.as_ref()?;
+        if *stored_frame != frame - 1 {
+            return None;
+        }
+        limits
+            .iter()
+            .find(|(a, _)| *a == axis)
+            .map(|(_, range)| *range)
+    })
+}
+
+/// Store the limits of the first X and Y axes and the size of the plot area in pixels the plot
+/// with the given title had on this frame.
+pub(crate) fn store_view(title: &CString, limits: ImPlotRect, size: ImVec2) {
+    with_state(title, |state| state.view = Some((limits, size)));
+}
+
+/// Get the limits and plot area size the plot with the given title had on the most recent frame
+/// they were stored.
+pub(crate) fn previous_view(title: &CString) -> Option<(ImPlotRect, ImVec2)> {
+    read_state(title, |state| state.view)
+}
+
+/// Record that the plot with the given title is begun on the current frame. Returns whether the
+/// plot is appearing, i.e. whether it was not begun on the previous frame, for example because
+/// it is in a collapsed header or a tab that was just opened again.
+pub(crate) fn mark_shown(title: &CString) -> bool {
+    CURRENT_TITLE.with(|current| *current.borrow_mut() = Some(title.clone()));
+    let frame = unsafe { sys::igGetFrameCount() };
+    with_state(title, |state| {
+        let previous = state.last_shown_frame.replace(frame);
+        !previous.is_some_and(|previous| previous >= frame - 1)
+    })
+}
+
+/// Record the frame of the plot that was just begun. BeginPlot adds the frame as the last imgui
+/// item, so this has to be called right after it.
+pub(crate) fn store_current_frame() -> (ImVec2, ImVec2) {
+    let mut frame_min = ImVec2 { x: 0.0, y: 0.0 };
+    let mut frame_max = ImVec2 { x: 0.0, y: 0.0 };
+    unsafe {
+        sys::igGetItemRectMin(&mut frame_min as *mut ImVec2);
+        sys::igGetItemRectMax(&mut frame_max as *mut ImVec2);
+    }
+    CURRENT_FRAME.with(|frame| frame.set((frame_min, frame_max)));
+    (frame_min, frame_max)
+}
+
+/// Get the frame of the plot that was begun most recently, see [`store_current_frame`].
+pub(crate) fn current_frame() -> (ImVec2, ImVec2) {
+    CURRENT_FRAME.with(Cell::get)
+}
+
+/// Make the plot with the given title, which was just begun, the active one if the user clicks
+/// or scrolls in its plot area on this frame. This locks the setup of the plot, so it has to be
+/// called after all setup calls.
+pub(crate) fn update_active_plot(title: &CString) {
+    if !unsafe { sys::ImPlot_IsPlotHovered() } {
+        return;
+    }
+    let io = unsafe { &*sys::igGetIO() };
+    let clicked = io.MouseClicked.iter().any(|clicked| *clicked);
+    let scrolled = io.MouseWheel != 0.0 || io.MouseWheelH != 0.0;
+    if clicked || scrolled {
+        ACTIVE_TITLE.with(|active| *active.borrow_mut() = Some(title.clone()));
+    }
+}
+
+/// Whether the plot with the given title is the one the user interacted with most recently.
+pub(crate) fn is_active_plot(title: &CString) -> bool {
+    ACTIVE_TITLE.with(|active| active.borrow().as_ref() == Some(title))
+}
+
+/// Whether the plot that was begun most recently is the one the user interacted with most
+/// recently.
+pub(crate) fn is_current_plot_active() -> bool {
+    CURRENT_TITLE.with(|current| current.borrow().as_ref().is_some_and(is_active_plot))
+}
+
+/// Store category labels for the given axis of the current plot, to be set up as ticks when the
+/// plot is begun on the next frame. Labels stored for the same axis before are replaced.
+pub(crate) fn store_category_ticks(axis: Axis, labels: Vec<CString>) {
+    let title = match CURRENT_TITLE.with(|current| current.borrow().clone()) {
+        Some(title) => title,
+        None => return,
+    };
+    with_state(&title, |state| {
+        state.category_ticks.retain(|(a, _)| *a != axis);
+        state.category_ticks.push((axis, labels));
+    });
+}
+
+/// Take the category labels stored for the plot with the given title on the previous frame.
+pub(crate) fn take_category_ticks(title: &CString) -> Vec<(Axis, Vec<CString>)> {
+    PLOT_STATES.with(|states| {
+        states
+            .borrow_mut()
+            .get_mut(title)
+            .map(|state| std::mem::take(&mut state.category_ticks))
+            .unwrap_or_default()
+    })
+}
@@ -0,0 +1,130 @@
+//! # Quantity module
+//!
+//! This module defines the [`Quantity`] trait for values that carry a physical unit in their
+//! type, so they can be plotted directly and the unit ends up in the axis label, as in
+//! "Distance [m]". With the `uom` feature enabled, it is implemented for the common SI quantities
+//! of the [uom](https://crates.io/crates/uom) crate.
+use crate::scratch::ScratchBuffer;
+
+/// A value with a unit that is known from its type. Plot elements take slices of quantities with
+/// methods like [`PlotLine::plot_quantities`](struct.PlotLine.html#method.plot_quantities), and
+/// plots get matching axis labels with
+/// [`Plot::with_x_axis_quantity`](struct.Plot.html#method.with_x_axis_quantity). Both use the
+/// same unit, so data and labels can not get out of sync.
+///
+/// A minimal implementation for a newtype looks like this:
+/// ```no_run
+/// struct Meters(f64);
+///
+/// impl implot::Quantity for Meters {
+///     fn unit() -> &'static str {
+///         "m"
+///     }
+///
+///     fn value(&self) -> f64 {
+///         self.0
+///     }
+/// }
+/// ```
+pub trait Quantity {
+    /// Symbol of the unit that [`Quantity::value`] is expressed in, for example "m". An empty
+    /// symbol means the quantity has no unit, and labels are left unchanged.
+    fn unit() -> &'static str;
+
+    /// The value in the unit given by [`Quantity::unit`].
+    fn value(&self) -> f64;
+}
+
+/// Make the axis label for a quantity, with the unit appended in brackets.
+pub(crate) fn quantity_label<Q: Quantity>(label: &str) -> String {
+    match Q::unit() {
+        "" => label.to_string(),
+        unit if label.is_empty() => format!("[{}]", unit),
+        unit => format!("{} [{}]", label, unit),
+    }
+}
+
+/// Convert quantities to their values, in a buffer borrowed from the scratch pool.
+pub(crate) fn quantity_values<Q: Quantity>(quantities: &[Q]) -> ScratchBuffer {
+    quantities.iter().map(Quantity::value).collect()
+}
+
+/// Implement [`Quantity`] for uom quantities. uom stores the value of a quantity in the base
+/// unit of its system, so the unit symbol is the one of the SI base or derived unit. Symbols are
+/// kept to Latin-1, which ImGui's default font covers, so "Ω" is spelled "Ohm".
+#[cfg(feature = "uom")]
+macro_rules! impl_uom_quantity {
+    ($($quantity:ident => $unit:expr),* $(,)?) => {
+        $(
+            impl Quantity for uom::si::f64::$quantity {
+                fn unit() -> &'static str {
+                    $unit
+                }
+
+                fn value(&self) -> f64 {
+                    self.value
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "uom")]
+impl_uom_quantity! {
+    Acceleration => "m/s²",
+    Angle => "rad",
+    ElectricCharge => "C",
+    ElectricCurrent => "A",
+    ElectricPotential => "V",
+    ElectricalResistance => "Ohm",
+    Energy => "J",
+    Force => "N",
+    Frequency => "Hz",
+    Length => "m",
+    Mass => "kg",
+    Power => "W",
+    Pressure => "Pa",
+    ThermodynamicTemperature => "K",
+    Time => "s",
+    Velocity => "m/s",
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Meters(f64);
+
+    impl Quantity for Meters {
+        fn unit() -> &'static str {
+            "m"
+        }
+
+        fn value(&self) -> f64 {
+            self.0
+        }
+    }
+
+    struct Ratio;
+
+    impl Quantity for Ratio {
+        fn unit() -> &'static str {
+            ""
+        }
+
+        fn value(&self) -> f64 {
+            1.0
+        }
+    }
+
+    #[test]
+    fn quantity_labels_and_values() {
+        assert_eq!(quantity_label::<Meters>("Distance"), "Distance [m]");
+        assert_eq!(quantity_label::<Meters>(""), "[m]");
+        assert_eq!(quantity_label::<Ratio>("Gain"), "Gain");
+        assert_eq!(
+            *quantity_values(&[Meters(1.5), Meters(-2.0)]),
+            vec![1.5, -2.0]
+        );
+    }
+}
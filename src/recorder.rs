@@ -0,0 +1,234 @@
+//! # Recorder module
+//!
+//! This module defines [`PlotRecorder`], which records the plot element calls made while it
+//! records, for comparing the plotting logic of two runs in tests without looking at pixels. The
+//! calls are collected by the items module, which sees every item plot elements add.
+
+/// One plot element call recorded by a [`PlotRecorder`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedCall {
+    /// Label of the item as given to the plot element, including any hidden `##` part
+    pub label: String,
+    /// Name of the plot element type the item was plotted with, for example "PlotLine"
+    pub element_type: String,
+    /// Number of data points plotted with the call. For heatmaps, this is the number of cells.
+    pub point_count: usize,
+    /// Points the plot element reported for the call as `[x, y]`, in plot coordinates. For most
+    /// elements, these are the plotted data points themselves, but some report other points that
+    /// span the same area, for example the corners of a heatmap.
+    pub points: Vec<[f64; 2]>,
+}
+
+impl PartialEq for RecordedCall {
+    /// Calls are equal if all their fields are. Points are compared exactly, except that NaN
+    /// coordinates are equal to each other, since they are often used to separate segments.
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label
+            && self.element_type == other.element_type
+            && self.point_count == other.point_count
+            && self.points.len() == other.points.len()
+            && self
+                .points
+                .iter()
+                .zip(other.points.iter())
+                .all(|(a, b)| same_coordinate(a[0], b[0]) && same_coordinate(a[1], b[1]))
+    }
+}
+
+/// Internal helper to compare coordinates, treating NaNs as equal to each other.
+fn same_coordinate(a: f64, b: f64) -> bool {
+    a == b || (a.is_nan() && b.is_nan())
+}
+
+/// The plot element calls recorded by a [`PlotRecorder`], in the order they were made. With the
+/// `serde` feature enabled, it can be serialized, for example to store it as a golden file.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlotRecording {
+    /// The recorded calls
+    pub calls: Vec<RecordedCall>,
+}
+
+impl PlotRecording {
+    /// Describe the differences between this recording and `other`, one line per difference,
+    /// such as `call 2: label "Sine" != "Cosine"`. The result is empty if the recordings
+    /// are equal, so it can be checked with `assert!(diff.is_empty(), "{:#?}", diff)`.
+    pub fn diff(&self, other: &PlotRecording) -> Vec<String> {
+        let mut differences = Vec::new();
+        for (index, (a, b)) in self.calls.iter().zip(other.calls.iter()).enumerate() {
+            if a.label != b.label {
+                differences.push(format!(
+                    "call {}: label {:?} != {:?}",
+                    index, a.label, b.label
+                ));
+            }
+            if a.element_type != b.element_type {
+                differences.push(format!(
+                    "call {}: element type {} != {}",
+                    index, a.element_type, b.element_type
+                ));
+            }
+            if a.point_count != b.point_count {
+                differences.push(format!(
+                    "call {}: point count {} != {}",
+                    index, a.point_count, b.point_count
+                ));
+            }
+            let first_different_point =
+                a.points.iter().zip(b.points.iter()).position(|(a, b)| {
+                    !same_coordinate(a[0], b[0]) || !same_coordinate(a[1], b[1])
+                });
+            if let Some(point) = first_different_point {
+                differences.push(format!(
+                    "call {}: point {} {:?} != {:?}",
+                    index, point, a.points[point], b.points[point]
+                ));
+            } else if a.points.len() != b.points.len() {
+                differences.push(format!(
+                    "call {}: {} points != {} points",
+                    index,
+                    a.points.len(),
+                    b.points.len()
+                ));
+            }
+        }
+        if self.calls.len() != other.calls.len() {
+            differences.push(format!(
+                "{} calls != {} calls",
+                self.calls.len(),
+                other.calls.len()
+            ));
+        }
+        differences
+    }
+}
+
+/// Records the plot element calls made while it records, with their labels, element types and
+/// data, into a [`PlotRecording`]. Comparing recordings of two runs, or a recording with a stored
+/// one, tests plotting logic without comparing rendered pixels:
+/// ```no_run
+/// # use implot::{Plot, PlotLine, PlotRecorder, PlotUi};
+/// # fn draw(plot_ui: &PlotUi, expected: &implot::PlotRecording) {
+/// let mut recorder = PlotRecorder::new();
+/// Plot::new("Signal").build(plot_ui, || {
+///     recorder.record(|| PlotLine::new("Sine").plot(&[0.0, 1.0], &[0.0, 0.84]));
+/// });
+/// let differences = recorder.recording().diff(expected);
+/// assert!(differences.is_empty(), "{:#?}", differences);
+/// # }
+/// ```
+///
+/// The calls still draw the plot as usual, so recording happens within a frame and a plot. Every
+/// item added to the plot is recorded as one call, including the items of helpers built on plot
+/// elements. Recorders can be nested, in which case calls are only recorded by the innermost one.
+#[derive(Debug, Default)]
+pub struct PlotRecorder {
+    /// Calls recorded so far
+    recording: PlotRecording,
+}
+
+impl PlotRecorder {
+    /// Create a recorder with an empty recording.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `build` and add the plot element calls it makes to the recording. This can be done
+    /// several times, for example for several plots or frames, to record them in sequence.
+    pub fn record<R>(&mut self, build: impl FnOnce() -> R) -> R {
+        let guard = CallRecordingGuard {
+            previous: Some(crate::items::begin_call_recording()),
+        };
+        let result = build();
+        self.recording.calls.extend(guard.finish());
+        result
+    }
+
+    /// Get the calls recorded so far.
+    pub fn recording(&self) -> &PlotRecording {
+        &self.recording
+    }
+
+    /// Take the recording, leaving the recorder with an empty one.
+    pub fn take_recording(&mut self) -> PlotRecording {
+        std::mem::take(&mut self.recording)
+    }
+}
+
+/// Ends the call recording it was created for, making the previous one active again. If `build`
+/// panics, this happens when the guard is dropped during unwinding, so recording does not stay
+/// on for the rest of the thread.
+struct CallRecordingGuard {
+    /// The recording that was active before, until it is made active again
+    previous: Option<Option<Vec<RecordedCall>>>,
+}
+
+impl CallRecordingGuard {
+    /// End the recording and return the recorded calls.
+    fn finish(mut self) -> Vec<RecordedCall> {
+        let previous = self.previous.take().unwrap_or_default();
+        crate::items::end_call_recording(previous)
+    }
+}
+
+impl Drop for CallRecordingGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            crate::items::end_call_recording(previous);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(label: &str, points: Vec<[f64; 2]>) -> RecordedCall {
+        RecordedCall {
+            label: label.to_string(),
+            element_type: "PlotLine".to_string(),
+            point_count: points.len(),
+            points,
+        }
+    }
+
+    #[test]
+    fn panic_ends_recording() {
+        let mut recorder = PlotRecorder::new();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            recorder.record(|| panic!("build exploded"))
+        }));
+        assert!(result.is_err());
+        assert!(!crate::items::records_calls());
+    }
+
+    #[test]
+    fn recording_differences() {
+        let a = PlotRecording {
+            calls: vec![
+                line("Sine", vec![[0.0, 0.0], [f64::NAN, f64::NAN], [1.0, 0.5]]),
+                line("Cosine", vec![[0.0, 1.0]]),
+            ],
+        };
+        assert_eq!(a, a.clone());
+        assert!(a.diff(&a).is_empty());
+
+        let b = PlotRecording {
+            calls: vec![
+                line("Sine", vec![[0.0, 0.0], [f64::NAN, f64::NAN], [1.0, 0.25]]),
+                line("Tangent", vec![[0.0, 1.0]]),
+                line("Extra", vec![]),
+            ],
+        };
+        assert_ne!(a, b);
+        assert_eq!(
+            a.diff(&b),
+            vec![
+                "call 0: point 2 [1.0, 0.5] != [1.0, 0.25]",
+                "call 1: label \"Cosine\" != \"Tangent\"",
+                "2 calls != 3 calls",
+            ]
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! # Scratch module
+//!
+//! This module keeps a small pool of `f64` buffers that plotting helpers borrow for temporary
+//! data, such as sorted, filtered or smoothed copies of the data they are given. Buffers go back
+//! into the pool when they are dropped and keep their capacity, so once they have grown to the
+//! sizes a frame needs, the helpers no longer allocate. Like ImGui and ImPlot themselves, this is
+//! meant to be used from a single thread: each thread has its own pool, and the pool of the
+//! thread the `Context` lives on is released when the context is dropped.
+use std::{
+    cell::RefCell,
+    iter::FromIterator,
+    ops::{Deref, DerefMut},
+};
+
+/// Maximum number of buffers kept in the pool, which bounds the memory held on to between
+/// frames. More buffers can be borrowed at once, the extra ones are just freed when dropped.
+const MAX_POOLED_BUFFERS: usize = 8;
+
+thread_local! {
+    /// Buffers that are not borrowed right now, all of them empty
+    static POOL: RefCell<Vec<Vec<f64>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A buffer borrowed from the pool, which is empty when borrowed and goes back into the pool
+/// when dropped. It can be used like a `Vec<f64>`.
+pub(crate) struct ScratchBuffer {
+    buffer: Vec<f64>,
+}
+
+impl ScratchBuffer {
+    /// Borrow an empty buffer from the pool, or create one if the pool is empty.
+    pub(crate) fn new() -> Self {
+        Self {
+            buffer: POOL.with(|pool| pool.borrow_mut().pop()).unwrap_or_default(),
+        }
+    }
+}
+
+impl FromIterator<f64> for ScratchBuffer {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut scratch = Self::new();
+        scratch.buffer.extend(iter);
+        scratch
+    }
+}
+
+impl Deref for ScratchBuffer {
+    type Target = Vec<f64>;
+
+    fn deref(&self) -> &Vec<f64> {
+        &self.buffer
+    }
+}
+
+impl DerefMut for ScratchBuffer {
+    fn deref_mut(&mut self) -> &mut Vec<f64> {
+        &mut self.buffer
+    }
+}
+
+impl Drop for ScratchBuffer {
+    fn drop(&mut self) {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.clear();
+        // The pool is gone while the thread is shutting down, the buffer is just freed then
+        let _ = POOL.try_with(|pool| {
+            let mut pool = pool.borrow_mut();
+            if pool.len() < MAX_POOLED_BUFFERS {
+                pool.push(buffer);
+            }
+        });
+    }
+}
+
+/// Free all pooled buffers of the current thread.
+pub(crate) fn release() {
+    POOL.with(|pool| pool.borrow_mut().clear());
+}
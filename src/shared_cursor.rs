@@ -0,0 +1,103 @@
+//! # Shared cursor module
+//!
+//! This module defines [`SharedCursorX`], a helper for showing a vertical cursor line at the same
+//! X position in several plots, following the mouse in whichever of them is hovered.
+use crate::{get_plot_mouse_position, is_plot_hovered, sys, Axis, ImVec4};
+use std::{cell::Cell, ffi::CString, rc::Rc};
+
+/// Draws a vertical line at a shared X position in a plot, and sets that position to the mouse
+/// X position while the plot is hovered. Give each of a group of plots (usually plots with
+/// linked X axes) its own `SharedCursorX` created from the same shared cell, and they all show
+/// the cursor where the mouse is in the hovered one, like the synchronized cursors of profiling
+/// tools. The cursor is removed when the mouse leaves the plot that placed it.
+///
+/// Keep these around across frames, and use them like this:
+/// ```no_run
+/// # use implot::{Plot, PlotLine, PlotUi, SharedCursorX};
+/// # fn draw(plot_ui: &PlotUi, cursors: &mut [SharedCursorX; 2], x: &[f64], y: &[f64]) {
+/// // Created once with SharedCursorX::new(position.clone()) for both plots, where position is
+/// // an Rc<Cell<Option<f64>>>
+/// Plot::new("Top").build(plot_ui, || {
+///     PlotLine::new("Signal").plot(x, y);
+///     cursors[0].plot();
+/// });
+/// Plot::new("Bottom").build(plot_ui, || {
+///     PlotLine::new("Signal").plot(x, y);
+///     cursors[1].plot();
+/// });
+/// # }
+/// ```
+///
+/// Plots built before the hovered plot in a frame show the position from the previous frame,
+/// which lags the mouse by one frame.
+#[derive(Debug, Clone)]
+pub struct SharedCursorX {
+    /// X position of the cursor in plot coordinates, shared with the other plots
+    position: Rc<Cell<Option<f64>>>,
+    /// Whether this plot set the current position, and with that has to remove it again
+    owns_position: bool,
+    /// Color of the cursor line
+    color: ImVec4,
+}
+
+impl SharedCursorX {
+    /// Create a cursor for one plot, sharing its position with the other cursors created from
+    /// clones of `position`. The position is `None` while no plot is hovered.
+    pub fn new(position: Rc<Cell<Option<f64>>>) -> Self {
+        Self {
+            position,
+            owns_position: false,
+            color: ImVec4 { x: 0.5, y: 0.5, z: 0.5, w: 1.0 },
+        }
+    }
+
+    /// Set the color of the cursor line, gray by default.
+    pub fn with_color(mut self, color: ImVec4) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Get the shared X position of the cursor, or `None` while no plot is hovered.
+    pub fn position(&self) -> Option<f64> {
+        self.position.get()
+    }
+
+    /// Update the shared position from the mouse if the current plot is hovered, and draw the
+    /// cursor line at the shared position, if there is one. The position is taken on the first
+    /// X and Y axes. The line is neither shown in the legend nor taken into account when fitting
+    /// the plot to its data. Call this in the closure passed to [`crate::Plot::build`], after
+    /// plotting the data so the line is drawn on top of it.
+    ///
+    /// This selects the first X and Y axes for upcoming plot elements, and ImPlot has no public
+    /// query to restore the axes that were selected before. Elements plotted after the cursor
+    /// against other axes need those axes selected again with [`crate::set_axis`].
+    #[rustversion::attr(since(1.48), doc(alias = "PlotInfLines"))]
+    pub fn plot(&mut self) {
+        if is_plot_hovered() {
+            self.position
+                .set(Some(get_plot_mouse_position(Axis::X1, Axis::Y1).X));
+            self.owns_position = true;
+        } else if self.owns_position {
+            self.position.set(None);
+            self.owns_position = false;
+        }
+
+        if let Some(x) = self.position.get() {
+            let label = CString::new("##shared cursor").unwrap();
+            let flags = sys::ImPlotItemFlags__ImPlotItemFlags_NoLegend
+                | sys::ImPlotItemFlags__ImPlotItemFlags_NoFit;
+            unsafe {
+                crate::set_first_axes();
+                sys::ImPlot_SetNextLineStyle(self.color, 1.0);
+                sys::ImPlot_PlotInfLines_doublePtr(
+                    label.as_ptr(),
+                    &x,
+                    1,
+                    flags,
+                    0,                                 // No offset
+                    std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+                );
+            }
+        }
+    }
+}